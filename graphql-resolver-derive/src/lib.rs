@@ -1,6 +1,10 @@
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse_macro_input, Attribute, DeriveInput, Expr, Lit, Meta};
+use syn::punctuated::Punctuated;
+use syn::{
+    parse_macro_input, Attribute, DeriveInput, Expr, FnArg, ItemFn, Lit, Meta, MetaNameValue, Pat,
+    Type,
+};
 
 #[proc_macro_derive(TraitResolver, attributes(resolver, batch_resolver))]
 pub fn derive_trait_resolver(input: TokenStream) -> TokenStream {
@@ -8,13 +12,22 @@ pub fn derive_trait_resolver(input: TokenStream) -> TokenStream {
     let name = &input.ident;
 
     if let Some(resolver_attr) = find_attribute(&input.attrs, "resolver") {
-        let resolver_name = extract_name_value(resolver_attr).unwrap_or_else(|| name.to_string());
-        return generate_resolver_impl(name, &resolver_name);
+        return match parse_resolver_attrs(resolver_attr) {
+            Ok(attrs) => {
+                let resolver_name = attrs.name.unwrap_or_else(|| name.to_string());
+                generate_resolver_impl(name, &resolver_name, attrs.args.as_ref())
+            }
+            Err(err) => err.to_compile_error().into(),
+        };
     }
 
     if let Some(batch_attr) = find_attribute(&input.attrs, "batch_resolver") {
-        let (resolver_name, batch_key) = extract_batch_resolver_attrs(batch_attr);
-        return generate_batch_resolver_registration(name, &resolver_name, &batch_key);
+        return match parse_batch_resolver_attrs(batch_attr) {
+            Ok((resolver_name, batch_key)) => {
+                generate_batch_resolver_impl(name, &resolver_name, &batch_key)
+            }
+            Err(err) => err.to_compile_error().into(),
+        };
     }
 
     syn::Error::new_spanned(name, "Missing #[resolver] or #[batch_resolver] attribute")
@@ -22,60 +35,117 @@ pub fn derive_trait_resolver(input: TokenStream) -> TokenStream {
         .into()
 }
 
+#[proc_macro_attribute]
+pub fn resolver(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let attrs =
+        parse_macro_input!(attr with Punctuated::<MetaNameValue, syn::Token![,]>::parse_terminated);
+    let func = parse_macro_input!(item as ItemFn);
+    match generate_fn_resolver(attrs, &func) {
+        Ok(expanded) => expanded.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
 fn find_attribute<'a>(attrs: &'a [Attribute], name: &str) -> Option<&'a Attribute> {
     attrs.iter().find(|attr| attr.path().is_ident(name))
 }
 
-fn extract_name_value(attr: &Attribute) -> Option<String> {
-    let meta = attr.meta.clone();
-    if let Meta::List(list) = meta {
-        let nested: Result<syn::punctuated::Punctuated<Meta, syn::Token![,]>, _> =
-            list.parse_args_with(syn::punctuated::Punctuated::parse_terminated);
-        if let Ok(nested) = nested {
-            for meta in nested {
-                if let Meta::NameValue(nv) = meta {
-                    if nv.path.is_ident("name") {
-                        if let Expr::Lit(expr_lit) = &nv.value {
-                            if let Lit::Str(lit_str) = &expr_lit.lit {
-                                return Some(lit_str.value());
-                            }
-                        }
-                    }
-                }
-            }
+struct ResolverAttrs {
+    name: Option<String>,
+    args: Option<Type>,
+}
+
+fn parse_resolver_attrs(attr: &Attribute) -> syn::Result<ResolverAttrs> {
+    let mut attrs = ResolverAttrs {
+        name: None,
+        args: None,
+    };
+
+    let Meta::List(list) = &attr.meta else {
+        return Ok(attrs);
+    };
+    let nested =
+        list.parse_args_with(Punctuated::<MetaNameValue, syn::Token![,]>::parse_terminated)?;
+    for nv in nested {
+        if nv.path.is_ident("name") {
+            attrs.name = Some(lit_str_value(&nv)?);
+        } else if nv.path.is_ident("args") {
+            let value = &nv.value;
+            attrs.args = Some(syn::parse2(quote!(#value))?);
+        } else {
+            return Err(syn::Error::new_spanned(
+                &nv.path,
+                "Unknown resolver attribute, expected `name` or `args`",
+            ));
         }
     }
-    None
+    Ok(attrs)
 }
 
-fn extract_batch_resolver_attrs(attr: &Attribute) -> (String, String) {
-    let mut resolver_name = String::new();
-    let mut batch_key = String::new();
+fn lit_str_value(nv: &MetaNameValue) -> syn::Result<String> {
+    if let Expr::Lit(expr_lit) = &nv.value {
+        if let Lit::Str(lit_str) = &expr_lit.lit {
+            return Ok(lit_str.value());
+        }
+    }
+    Err(syn::Error::new_spanned(
+        &nv.value,
+        "Expected a string literal",
+    ))
+}
+
+fn parse_batch_resolver_attrs(attr: &Attribute) -> syn::Result<(String, String)> {
+    let mut resolver_name = None;
+    let mut batch_key = None;
 
     if let Meta::List(list) = &attr.meta {
-        let nested: Result<syn::punctuated::Punctuated<Meta, syn::Token![,]>, _> =
-            list.parse_args_with(syn::punctuated::Punctuated::parse_terminated);
-        if let Ok(nested) = nested {
-            for meta in nested {
-                if let Meta::NameValue(nv) = meta {
-                    if let Expr::Lit(expr_lit) = &nv.value {
-                        if let Lit::Str(lit_str) = &expr_lit.lit {
-                            if nv.path.is_ident("name") {
-                                resolver_name = lit_str.value();
-                            } else if nv.path.is_ident("batch_key") {
-                                batch_key = lit_str.value();
-                            }
-                        }
-                    }
-                }
+        let nested =
+            list.parse_args_with(Punctuated::<MetaNameValue, syn::Token![,]>::parse_terminated)?;
+        for nv in nested {
+            let value = lit_str_value(&nv)?;
+            if value.is_empty() {
+                return Err(syn::Error::new_spanned(
+                    &nv.value,
+                    "Value must not be empty",
+                ));
+            }
+            if nv.path.is_ident("name") {
+                resolver_name = Some(value);
+            } else if nv.path.is_ident("batch_key") {
+                batch_key = Some(value);
+            } else {
+                return Err(syn::Error::new_spanned(
+                    &nv.path,
+                    "Unknown batch_resolver attribute, expected `name` or `batch_key`",
+                ));
             }
         }
     }
 
-    (resolver_name, batch_key)
+    let resolver_name = resolver_name
+        .ok_or_else(|| syn::Error::new_spanned(attr, "Missing `name` in #[batch_resolver(...)]"))?;
+    let batch_key = batch_key.ok_or_else(|| {
+        syn::Error::new_spanned(attr, "Missing `batch_key` in #[batch_resolver(...)]")
+    })?;
+    Ok((resolver_name, batch_key))
 }
 
-fn generate_resolver_impl(name: &syn::Ident, resolver_name: &str) -> TokenStream {
+fn generate_resolver_impl(
+    name: &syn::Ident,
+    resolver_name: &str,
+    args_type: Option<&Type>,
+) -> TokenStream {
+    let (args_type, args) = match args_type {
+        Some(args_type) => (
+            quote!(#args_type),
+            quote!(::graphql_resolver::__private::deserialize_args::<#args_type>(#resolver_name, args)?),
+        ),
+        None => (
+            quote!(::graphql_resolver::FxHashMap<String, ::graphql_resolver::__private::Value>),
+            quote!(args),
+        ),
+    };
+
     let expanded = quote! {
         impl ::graphql_resolver::Resolver for #name {
             fn name(&self) -> &'static str {
@@ -85,9 +155,13 @@ fn generate_resolver_impl(name: &syn::Ident, resolver_name: &str) -> TokenStream
             fn resolve<'a>(
                 &'a self,
                 ctx: &'a ::graphql_resolver::ResolverContext,
-                args: ::std::collections::HashMap<String, ::async_graphql::Value>,
-            ) -> ::graphql_resolver::BoxFuture<'a, ::graphql_resolver::ResolverResult<::async_graphql::Value>> {
-                Box::pin(self.execute(ctx, args))
+                args: ::graphql_resolver::FxHashMap<String, ::graphql_resolver::__private::Value>,
+            ) -> ::graphql_resolver::BoxFuture<'a, ::graphql_resolver::ResolverResult<::graphql_resolver::__private::Value>> {
+                Box::pin(async move {
+                    let args = #args;
+                    let output = <Self as ::graphql_resolver::ExecuteResolver<#args_type>>::execute(self, ctx, args).await?;
+                    ::graphql_resolver::__private::serialize_output(#resolver_name, output)
+                })
             }
         }
 
@@ -100,12 +174,45 @@ fn generate_resolver_impl(name: &syn::Ident, resolver_name: &str) -> TokenStream
     TokenStream::from(expanded)
 }
 
-fn generate_batch_resolver_registration(
+fn generate_batch_resolver_impl(
     name: &syn::Ident,
     resolver_name: &str,
     batch_key: &str,
 ) -> TokenStream {
     let expanded = quote! {
+        impl ::graphql_resolver::BatchResolver for #name {
+            type Key = <Self as ::graphql_resolver::LoadBatchResolver>::Key;
+            type Value = <Self as ::graphql_resolver::LoadBatchResolver>::Value;
+
+            fn load<'a>(
+                &'a self,
+                ctx: &'a ::graphql_resolver::ResolverContext,
+                keys: Vec<Self::Key>,
+            ) -> ::graphql_resolver::BoxFuture<'a, ::graphql_resolver::ResolverResult<Vec<(Self::Key, Self::Value)>>> {
+                <Self as ::graphql_resolver::LoadBatchResolver>::load(self, ctx, keys)
+            }
+
+            fn load_each<'a>(
+                &'a self,
+                ctx: &'a ::graphql_resolver::ResolverContext,
+                keys: Vec<Self::Key>,
+            ) -> ::graphql_resolver::BoxFuture<'a, ::graphql_resolver::ResolverResult<Vec<(Self::Key, ::graphql_resolver::ResolverResult<Self::Value>)>>> {
+                <Self as ::graphql_resolver::LoadBatchResolver>::load_each(self, ctx, keys)
+            }
+
+            fn name(&self) -> &'static str {
+                #resolver_name
+            }
+
+            fn batch_key_field(&self) -> &'static str {
+                #batch_key
+            }
+
+            fn cache_results(&self) -> bool {
+                <Self as ::graphql_resolver::LoadBatchResolver>::cache_results(self)
+            }
+        }
+
         ::graphql_resolver::inventory::submit! {
             ::graphql_resolver::BatchResolverRegistration::new(|| {
                 Box::new(#name::default()) as Box<dyn ::graphql_resolver::ErasedBatchResolver>
@@ -114,3 +221,210 @@ fn generate_batch_resolver_registration(
     };
     TokenStream::from(expanded)
 }
+
+fn generate_fn_resolver(
+    attrs: Punctuated<MetaNameValue, syn::Token![,]>,
+    func: &ItemFn,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let sig = &func.sig;
+    if sig.asyncness.is_none() {
+        return Err(syn::Error::new_spanned(
+            sig.fn_token,
+            "Resolver functions must be async",
+        ));
+    }
+    if !sig.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &sig.generics,
+            "Resolver functions cannot be generic",
+        ));
+    }
+
+    let mut resolver_name = sig.ident.to_string();
+    for nv in &attrs {
+        if nv.path.is_ident("name") {
+            resolver_name = lit_str_value(nv)?;
+        } else {
+            return Err(syn::Error::new_spanned(
+                &nv.path,
+                "Unknown resolver attribute, expected `name`",
+            ));
+        }
+    }
+
+    // A `&ResolverContext` parameter receives the context; every other
+    // parameter is taken from the argument with its camelCase name.
+    let mut extracts = Vec::new();
+    let mut call_args = Vec::new();
+    for input in &sig.inputs {
+        let FnArg::Typed(pat_type) = input else {
+            return Err(syn::Error::new_spanned(
+                input,
+                "Resolver functions cannot take `self`",
+            ));
+        };
+        if let Type::Reference(reference) = &*pat_type.ty {
+            if !is_resolver_context(&reference.elem) {
+                return Err(syn::Error::new_spanned(
+                    &pat_type.ty,
+                    "Only `&ResolverContext` can be taken by reference; use an owned type such as `String` for arguments",
+                ));
+            }
+            call_args.push(quote!(ctx));
+            continue;
+        }
+        let Pat::Ident(pat_ident) = &*pat_type.pat else {
+            return Err(syn::Error::new_spanned(
+                &pat_type.pat,
+                "Resolver parameters must be plain identifiers",
+            ));
+        };
+        let ident = &pat_ident.ident;
+        let ty = &pat_type.ty;
+        let arg_name = to_camel_case(&ident.to_string());
+        extracts.push(quote! {
+            let #ident: #ty = ::graphql_resolver::__private::extract_arg(#resolver_name, &mut args, #arg_name)?;
+        });
+        call_args.push(quote!(#ident));
+    }
+
+    let vis = &func.vis;
+    let fn_name = &sig.ident;
+    let struct_name = syn::Ident::new(
+        &format!("{}Resolver", to_pascal_case(&fn_name.to_string())),
+        fn_name.span(),
+    );
+
+    Ok(quote! {
+        #func
+
+        #[derive(Default, Clone, Copy)]
+        #vis struct #struct_name;
+
+        impl ::graphql_resolver::Resolver for #struct_name {
+            fn name(&self) -> &'static str {
+                #resolver_name
+            }
+
+            #[allow(unused_mut, unused_variables)]
+            fn resolve<'a>(
+                &'a self,
+                ctx: &'a ::graphql_resolver::ResolverContext,
+                mut args: ::graphql_resolver::FxHashMap<String, ::graphql_resolver::__private::Value>,
+            ) -> ::graphql_resolver::BoxFuture<'a, ::graphql_resolver::ResolverResult<::graphql_resolver::__private::Value>> {
+                Box::pin(async move {
+                    #(#extracts)*
+                    let output = #fn_name(#(#call_args),*).await?;
+                    ::graphql_resolver::__private::serialize_output(#resolver_name, output)
+                })
+            }
+        }
+
+        ::graphql_resolver::inventory::submit! {
+            ::graphql_resolver::ResolverRegistration::new(|| {
+                Box::new(#struct_name) as Box<dyn ::graphql_resolver::Resolver>
+            }, #resolver_name)
+        }
+    })
+}
+
+fn is_resolver_context(ty: &Type) -> bool {
+    match ty {
+        Type::Path(type_path) => type_path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "ResolverContext"),
+        _ => false,
+    }
+}
+
+fn to_camel_case(name: &str) -> String {
+    let pascal = to_pascal_case(name);
+    let mut chars = pascal.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn to_pascal_case(name: &str) -> String {
+    name.split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand(func: &str) -> syn::Result<proc_macro2::TokenStream> {
+        let func: ItemFn = syn::parse_str(func).unwrap();
+        generate_fn_resolver(Punctuated::new(), &func)
+    }
+
+    #[test]
+    fn test_fn_resolver_binds_context_reference() {
+        let tokens = expand(
+            "async fn get_user(ctx: &ResolverContext, user_id: String) -> ResolverResult<String> { Ok(user_id) }",
+        )
+        .unwrap()
+        .to_string();
+        assert!(tokens.contains("get_user (ctx , user_id)"));
+    }
+
+    #[test]
+    fn test_fn_resolver_rejects_other_references() {
+        let err =
+            expand("async fn greet(name: &str) -> ResolverResult<String> { Ok(name.to_string()) }")
+                .unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("Only `&ResolverContext` can be taken by reference"));
+    }
+
+    fn batch_attrs_error(attr: Attribute) -> String {
+        parse_batch_resolver_attrs(&attr).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn test_batch_resolver_attrs() {
+        let attr: Attribute =
+            syn::parse_quote!(#[batch_resolver(name = "getAuthors", batch_key = "authorId")]);
+        assert_eq!(
+            parse_batch_resolver_attrs(&attr).unwrap(),
+            ("getAuthors".to_string(), "authorId".to_string())
+        );
+    }
+
+    #[test]
+    fn test_batch_resolver_attrs_errors() {
+        assert_eq!(
+            batch_attrs_error(syn::parse_quote!(#[batch_resolver(batch_key = "authorId")])),
+            "Missing `name` in #[batch_resolver(...)]"
+        );
+        assert_eq!(
+            batch_attrs_error(syn::parse_quote!(#[batch_resolver(name = "getAuthors")])),
+            "Missing `batch_key` in #[batch_resolver(...)]"
+        );
+        assert_eq!(
+            batch_attrs_error(syn::parse_quote!(
+                #[batch_resolver(name = "getAuthors", batch_key = "")]
+            )),
+            "Value must not be empty"
+        );
+        assert_eq!(
+            batch_attrs_error(syn::parse_quote!(
+                #[batch_resolver(name = "getAuthors", batch_key = "authorId", cache = "no")]
+            )),
+            "Unknown batch_resolver attribute, expected `name` or `batch_key`"
+        );
+    }
+}
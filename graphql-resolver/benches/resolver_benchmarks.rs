@@ -749,6 +749,7 @@ fn bench_sdl_parsing(c: &mut Criterion) {
                 let server = GraphQLServer::builder()
                     .sdl(black_box(sdl))
                     .skip_n1_validation()
+                    .skip_resolver_validation()
                     .build();
                 black_box(server)
             });
@@ -773,6 +774,7 @@ fn bench_n1_detection(c: &mut Criterion) {
             let server = GraphQLServer::builder()
                 .sdl(black_box(COMPLEX_SDL))
                 .skip_n1_validation()
+                .skip_resolver_validation()
                 .build();
             black_box(server)
         });
@@ -783,6 +785,7 @@ fn bench_n1_detection(c: &mut Criterion) {
             let server = GraphQLServer::builder()
                 .sdl(black_box(DEEPLY_NESTED_SDL))
                 .skip_n1_validation()
+                .skip_resolver_validation()
                 .build();
             black_box(server)
         });
@@ -857,6 +860,7 @@ fn bench_server_builder(c: &mut Criterion) {
                     batch_key: "userId",
                 })
                 .skip_n1_validation()
+                .skip_resolver_validation()
                 .build();
             black_box(server)
         });
@@ -952,6 +956,7 @@ fn bench_scaling(c: &mut Criterion) {
                     let server = GraphQLServer::builder()
                         .sdl(black_box(sdl))
                         .skip_n1_validation()
+                        .skip_resolver_validation()
                         .build();
                     black_box(server)
                 });
@@ -1027,6 +1032,7 @@ fn bench_fake_example(c: &mut Criterion) {
         .register_batch_resolver(FakePostsBatchResolver)
         .register_batch_resolver(FakeCommentsBatchResolver)
         .skip_n1_validation()
+        .skip_resolver_validation()
         .build()
         .unwrap();
 
@@ -1190,6 +1196,7 @@ fn bench_schema_size_comparison(c: &mut Criterion) {
                 let server = GraphQLServer::builder()
                     .sdl(black_box(sdl))
                     .skip_n1_validation()
+                    .skip_resolver_validation()
                     .build();
                 black_box(server)
             });
@@ -0,0 +1,637 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+use async_graphql::dataloader::{DataLoader, Loader};
+use rustc_hash::FxHashMap;
+
+use crate::error::ResolverError;
+use crate::registry::resolver::{ContextData, ResolverContext, WeakContextData};
+use crate::registry::selection::{merge_selection, selection_covers, SelectedField};
+use crate::registry::storage::{ErasedBatchResolver, TraitRegistry};
+
+pub(crate) type BatchError = Arc<ResolverError>;
+
+type ErasedDataLoader = DataLoader<ErasedLoader>;
+
+// A key requested by one field: the path of the field, which the resolver
+// context of its batch is built from, and the serialized batch key.
+type FieldKey = (Vec<String>, String);
+
+type LoadResult = Result<serde_json::Value, BatchError>;
+
+// A cached result remembers the selection it was fetched with, so a field
+// selecting more than that reloads the key instead of reading missing fields
+// as null.
+struct CachedResult {
+    selection: Vec<SelectedField>,
+    value: LoadResult,
+}
+
+pub(crate) struct ErasedLoader {
+    resolver: Arc<dyn ErasedBatchResolver>,
+    selections: Mutex<FxHashMap<Vec<String>, Vec<SelectedField>>>,
+    results: Mutex<FxHashMap<String, CachedResult>>,
+    data: WeakContextData,
+}
+
+impl ErasedLoader {
+    fn merge_selection(&self, path: &[String], selection: &[SelectedField]) {
+        let mut selections = self
+            .selections
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        match selections.get_mut(path) {
+            Some(current) => merge_selection(current, selection),
+            None => {
+                selections.insert(path.to_vec(), selection.to_vec());
+            }
+        }
+    }
+
+    fn selection(&self, path: &[String]) -> Vec<SelectedField> {
+        self.selections
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(path)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn cached(&self, key: &str, selection: &[SelectedField]) -> Option<LoadResult> {
+        if !self.resolver.cache_results() {
+            return None;
+        }
+        self.results
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(key)
+            .filter(|cached| selection_covers(&cached.selection, selection))
+            .map(|cached| cached.value.clone())
+    }
+
+    async fn load_field(
+        &self,
+        path: &[String],
+        selection: Vec<SelectedField>,
+        keys: &[&String],
+    ) -> Result<Vec<(String, LoadResult)>, BatchError> {
+        let keys = keys
+            .iter()
+            .map(|key| serde_json::from_str(key))
+            .collect::<Result<Vec<serde_json::Value>, _>>()
+            .map_err(|e| Arc::new(ResolverError::from(e)))?;
+
+        let field_name = path.last().cloned().unwrap_or_default();
+        let ctx = ResolverContext::new(field_name)
+            .with_path(path.to_vec())
+            .with_selection(selection)
+            .with_data(self.data.upgrade());
+        let results = self
+            .resolver
+            .load_erased_each(&ctx, keys)
+            .await
+            .map_err(Arc::new)?;
+
+        results
+            .into_iter()
+            .map(|(key, value)| Ok((serde_json::to_string(&key)?, value.map_err(Arc::new))))
+            .collect::<Result<Vec<_>, serde_json::Error>>()
+            .map_err(|e| Arc::new(ResolverError::from(e)))
+    }
+}
+
+impl Loader<FieldKey> for ErasedLoader {
+    type Value = LoadResult;
+    type Error = BatchError;
+
+    async fn load(&self, keys: &[FieldKey]) -> Result<HashMap<FieldKey, LoadResult>, BatchError> {
+        // Every field gets its own call, so the resolver context carries that
+        // field's path and selection. Results are cached per key and shared
+        // by all fields using the resolver.
+        let mut fields: Vec<(&Vec<String>, Vec<&String>)> = Vec::new();
+        for (path, key) in keys {
+            match fields.iter_mut().find(|(field, _)| *field == path) {
+                Some((_, keys)) => keys.push(key),
+                None => fields.push((path, vec![key])),
+            }
+        }
+
+        let mut loaded = HashMap::new();
+        for (path, keys) in fields {
+            let selection = self.selection(path);
+            let mut missing = Vec::new();
+            for key in keys {
+                match self.cached(key, &selection) {
+                    Some(value) => {
+                        loaded.insert((path.clone(), key.clone()), value);
+                    }
+                    None => missing.push(key),
+                }
+            }
+            if missing.is_empty() {
+                continue;
+            }
+
+            // An error for the whole call only fails the keys of this field,
+            // and is not cached.
+            match self.load_field(path, selection.clone(), &missing).await {
+                Ok(results) => {
+                    for (key, value) in results {
+                        if self.resolver.cache_results() {
+                            let cached = CachedResult {
+                                selection: selection.clone(),
+                                value: value.clone(),
+                            };
+                            self.results
+                                .lock()
+                                .unwrap_or_else(PoisonError::into_inner)
+                                .insert(key.clone(), cached);
+                        }
+                        loaded.insert((path.clone(), key), value);
+                    }
+                }
+                Err(err) => {
+                    for key in missing {
+                        loaded.insert((path.clone(), key.clone()), Err(err.clone()));
+                    }
+                }
+            }
+        }
+        Ok(loaded)
+    }
+}
+
+pub(crate) struct BatchLoaders {
+    registry: Arc<TraitRegistry>,
+    delay: Duration,
+    max_batch_size: usize,
+    loaders: Mutex<FxHashMap<String, Arc<ErasedDataLoader>>>,
+}
+
+impl BatchLoaders {
+    pub fn new(registry: Arc<TraitRegistry>, delay: Duration, max_batch_size: usize) -> Self {
+        Self {
+            registry,
+            delay,
+            max_batch_size,
+            loaders: Mutex::new(FxHashMap::default()),
+        }
+    }
+
+    pub async fn load(
+        &self,
+        resolver_name: &str,
+        path: &[String],
+        selection: &[SelectedField],
+        key: &serde_json::Value,
+        data: &ContextData,
+    ) -> Result<Option<serde_json::Value>, BatchError> {
+        let loader = self.loader(resolver_name, data)?;
+        loader.loader().merge_selection(path, selection);
+        let key = serde_json::to_string(key).map_err(|e| Arc::new(ResolverError::from(e)))?;
+        loader.load_one((path.to_vec(), key)).await?.transpose()
+    }
+
+    pub fn clear(&self) {
+        self.loaders
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+
+    fn loader(
+        &self,
+        resolver_name: &str,
+        data: &ContextData,
+    ) -> Result<Arc<ErasedDataLoader>, BatchError> {
+        let mut loaders = self.loaders.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(loader) = loaders.get(resolver_name) {
+            return Ok(loader.clone());
+        }
+
+        let resolver = self
+            .registry
+            .get_batch_resolver(resolver_name)
+            .map_err(Arc::new)?;
+        let erased = ErasedLoader {
+            resolver,
+            selections: Mutex::new(FxHashMap::default()),
+            results: Mutex::new(FxHashMap::default()),
+            data: data.downgrade(),
+        };
+        let loader = DataLoader::new(erased, spawn_batch)
+            .delay(self.delay)
+            .max_batch_size(self.max_batch_size);
+
+        let loader = Arc::new(loader);
+        loaders.insert(resolver_name.to_string(), loader.clone());
+        Ok(loader)
+    }
+}
+
+fn spawn_batch(task: futures::future::BoxFuture<'static, ()>) {
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) => {
+            handle.spawn(task);
+        }
+        Err(_) => {
+            std::thread::spawn(move || futures::executor::block_on(task));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::resolver::{BoxFuture, ResolverResult};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct CountingBatchResolver {
+        calls: Arc<AtomicUsize>,
+        received: Arc<Mutex<Vec<serde_json::Value>>>,
+        cache_results: bool,
+    }
+
+    impl ErasedBatchResolver for CountingBatchResolver {
+        fn name(&self) -> &'static str {
+            "counting"
+        }
+
+        fn batch_key_field(&self) -> &'static str {
+            "id"
+        }
+
+        fn load_erased<'a>(
+            &'a self,
+            _ctx: &'a ResolverContext,
+            keys: Vec<serde_json::Value>,
+        ) -> BoxFuture<'a, ResolverResult<Vec<(serde_json::Value, serde_json::Value)>>> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            self.received.lock().unwrap().extend(keys.iter().cloned());
+            Box::pin(async move {
+                Ok(keys
+                    .into_iter()
+                    .map(|k| (k.clone(), serde_json::json!({ "key": k })))
+                    .collect())
+            })
+        }
+
+        fn cache_results(&self) -> bool {
+            self.cache_results
+        }
+    }
+
+    fn make_loaders(calls: Arc<AtomicUsize>, max_batch_size: usize) -> BatchLoaders {
+        make_loaders_with(calls, Arc::default(), max_batch_size, true)
+    }
+
+    fn make_loaders_with(
+        calls: Arc<AtomicUsize>,
+        received: Arc<Mutex<Vec<serde_json::Value>>>,
+        max_batch_size: usize,
+        cache_results: bool,
+    ) -> BatchLoaders {
+        let mut registry = TraitRegistry::new();
+        registry.register_batch_resolver(CountingBatchResolver {
+            calls,
+            received,
+            cache_results,
+        });
+        BatchLoaders::new(Arc::new(registry), Duration::from_millis(1), max_batch_size)
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_loads_are_coalesced() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let loaders = make_loaders(calls.clone(), 100);
+        let data = ContextData::default();
+        let path = vec!["User".to_string(), "posts".to_string()];
+
+        let keys: Vec<_> = (0..5).map(|i| serde_json::json!(i)).collect();
+        let results = futures::future::join_all(
+            keys.iter()
+                .map(|key| loaders.load("counting", &path, &[], key, &data)),
+        )
+        .await;
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        for (key, result) in keys.iter().zip(results) {
+            assert_eq!(result.unwrap(), Some(serde_json::json!({ "key": key })));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_max_batch_size_splits_batches() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let loaders = make_loaders(calls.clone(), 2);
+        let data = ContextData::default();
+        let path = vec!["User".to_string(), "posts".to_string()];
+
+        let keys: Vec<_> = (0..4).map(|i| serde_json::json!(i)).collect();
+        futures::future::join_all(
+            keys.iter()
+                .map(|key| loaders.load("counting", &path, &[], key, &data)),
+        )
+        .await;
+
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_load_unknown_resolver() {
+        let loaders = make_loaders(Arc::new(AtomicUsize::new(0)), 100);
+        let data = ContextData::default();
+        let result = loaders
+            .load("missing", &[], &[], &serde_json::json!(1), &data)
+            .await;
+
+        match result.unwrap_err().as_ref() {
+            ResolverError::NotFound(name) => assert_eq!(name, "missing"),
+            _ => panic!("Expected NotFound error"),
+        }
+    }
+
+    #[test]
+    fn test_load_without_runtime() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let loaders = make_loaders(calls.clone(), 100);
+        let data = ContextData::default();
+
+        let result = futures::executor::block_on(loaders.load(
+            "counting",
+            &[],
+            &[],
+            &serde_json::json!("a"),
+            &data,
+        ));
+
+        assert_eq!(result.unwrap(), Some(serde_json::json!({ "key": "a" })));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_keys_are_loaded_once() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let loaders = make_loaders_with(calls.clone(), received.clone(), 100, true);
+        let data = ContextData::default();
+
+        let keys = [
+            serde_json::json!("a"),
+            serde_json::json!("b"),
+            serde_json::json!("a"),
+            serde_json::json!("a"),
+        ];
+        let results = futures::future::join_all(
+            keys.iter()
+                .map(|key| loaders.load("counting", &[], &[], key, &data)),
+        )
+        .await;
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(received.lock().unwrap().len(), 2);
+        for (key, result) in keys.iter().zip(results) {
+            assert_eq!(result.unwrap(), Some(serde_json::json!({ "key": key })));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cached_key_is_not_reloaded() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let loaders = make_loaders(calls.clone(), 100);
+        let data = ContextData::default();
+        let key = serde_json::json!({ "id": 1 });
+
+        let first = loaders
+            .load("counting", &[], &[], &key, &data)
+            .await
+            .unwrap();
+        let second = loaders
+            .load("counting", &[], &[], &key, &data)
+            .await
+            .unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_cached_key_is_reloaded_for_a_wider_selection() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let loaders = make_loaders(calls.clone(), 100);
+        let data = ContextData::default();
+        let path = vec!["Post".to_string(), "author".to_string()];
+        let key = serde_json::json!(1);
+        let ids = [SelectedField::new("id")];
+        let names = [SelectedField::new("name")];
+
+        loaders
+            .load("counting", &path, &ids, &key, &data)
+            .await
+            .unwrap();
+        loaders
+            .load("counting", &path, &names, &key, &data)
+            .await
+            .unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        loaders
+            .load("counting", &path, &ids, &key, &data)
+            .await
+            .unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_clear_drops_cached_results() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let loaders = make_loaders(calls.clone(), 100);
+        let data = ContextData::default();
+        let key = serde_json::json!(1);
+
+        loaders
+            .load("counting", &[], &[], &key, &data)
+            .await
+            .unwrap();
+        loaders.clear();
+        loaders
+            .load("counting", &[], &[], &key, &data)
+            .await
+            .unwrap();
+
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_selections_are_merged_per_field() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let loaders = make_loaders(calls.clone(), 100);
+        let data = ContextData::default();
+        let author = vec!["Post".to_string(), "author".to_string()];
+        let editor = vec!["Post".to_string(), "editor".to_string()];
+        let ids = [SelectedField::new("id")];
+        let titles = [SelectedField::new("title")];
+
+        futures::future::join_all([
+            loaders.load("counting", &author, &ids, &serde_json::json!(1), &data),
+            loaders.load("counting", &author, &titles, &serde_json::json!(2), &data),
+            loaders.load("counting", &editor, &ids, &serde_json::json!(3), &data),
+        ])
+        .await;
+
+        let loader = loaders.loader("counting", &data).unwrap();
+        assert_eq!(
+            loader.loader().selection(&author),
+            [SelectedField::new("id"), SelectedField::new("title")]
+        );
+        assert_eq!(
+            loader.loader().selection(&editor),
+            [SelectedField::new("id")]
+        );
+    }
+
+    struct FieldBatchResolver {
+        calls: Arc<AtomicUsize>,
+    }
+
+    impl ErasedBatchResolver for FieldBatchResolver {
+        fn name(&self) -> &'static str {
+            "field"
+        }
+
+        fn batch_key_field(&self) -> &'static str {
+            "id"
+        }
+
+        fn load_erased<'a>(
+            &'a self,
+            ctx: &'a ResolverContext,
+            keys: Vec<serde_json::Value>,
+        ) -> BoxFuture<'a, ResolverResult<Vec<(serde_json::Value, serde_json::Value)>>> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Box::pin(async move {
+                Ok(keys
+                    .into_iter()
+                    .map(|k| (k, serde_json::json!({ "field": ctx.field_name() })))
+                    .collect())
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fields_sharing_a_resolver_share_results() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let mut registry = TraitRegistry::new();
+        registry.register_batch_resolver(FieldBatchResolver {
+            calls: calls.clone(),
+        });
+        let loaders = BatchLoaders::new(Arc::new(registry), Duration::from_millis(1), 100);
+        let data = ContextData::default();
+        let author = vec!["Post".to_string(), "author".to_string()];
+        let editor = vec!["Comment".to_string(), "editor".to_string()];
+
+        let results = futures::future::join_all([
+            loaders.load("field", &author, &[], &serde_json::json!(1), &data),
+            loaders.load("field", &editor, &[], &serde_json::json!(2), &data),
+        ])
+        .await;
+
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert_eq!(
+            results[0].as_ref().unwrap(),
+            &Some(serde_json::json!({ "field": "author" }))
+        );
+        assert_eq!(
+            results[1].as_ref().unwrap(),
+            &Some(serde_json::json!({ "field": "editor" }))
+        );
+
+        let cached = loaders
+            .load("field", &editor, &[], &serde_json::json!(1), &data)
+            .await
+            .unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert_eq!(cached, Some(serde_json::json!({ "field": "author" })));
+    }
+
+    struct PartialBatchResolver;
+
+    impl ErasedBatchResolver for PartialBatchResolver {
+        fn name(&self) -> &'static str {
+            "partial"
+        }
+
+        fn batch_key_field(&self) -> &'static str {
+            "id"
+        }
+
+        fn load_erased<'a>(
+            &'a self,
+            _ctx: &'a ResolverContext,
+            _keys: Vec<serde_json::Value>,
+        ) -> BoxFuture<'a, ResolverResult<Vec<(serde_json::Value, serde_json::Value)>>> {
+            Box::pin(async move { Err(ResolverError::Execution("unused".to_string())) })
+        }
+
+        fn load_erased_each<'a>(
+            &'a self,
+            _ctx: &'a ResolverContext,
+            keys: Vec<serde_json::Value>,
+        ) -> BoxFuture<
+            'a,
+            ResolverResult<Vec<(serde_json::Value, ResolverResult<serde_json::Value>)>>,
+        > {
+            Box::pin(async move {
+                Ok(keys
+                    .into_iter()
+                    .map(|key| match key.as_i64() {
+                        Some(0) => (key, Err(ResolverError::Execution("bad key".to_string()))),
+                        _ => (key.clone(), Ok(key)),
+                    })
+                    .collect())
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_per_key_errors_only_fail_their_keys() {
+        let mut registry = TraitRegistry::new();
+        registry.register_batch_resolver(PartialBatchResolver);
+        let loaders = BatchLoaders::new(Arc::new(registry), Duration::from_millis(1), 100);
+        let data = ContextData::default();
+
+        let keys: Vec<_> = (0..3).map(|i| serde_json::json!(i)).collect();
+        let results = futures::future::join_all(
+            keys.iter()
+                .map(|key| loaders.load("partial", &[], &[], key, &data)),
+        )
+        .await;
+
+        match results[0].as_ref().unwrap_err().as_ref() {
+            ResolverError::Execution(msg) => assert_eq!(msg, "bad key"),
+            _ => panic!("Expected Execution error"),
+        }
+        assert_eq!(results[1].as_ref().unwrap(), &Some(serde_json::json!(1)));
+        assert_eq!(results[2].as_ref().unwrap(), &Some(serde_json::json!(2)));
+    }
+
+    #[tokio::test]
+    async fn test_cache_opt_out_reloads_key() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let loaders = make_loaders_with(calls.clone(), Arc::default(), 100, false);
+        let data = ContextData::default();
+        let key = serde_json::json!(1);
+
+        loaders
+            .load("counting", &[], &[], &key, &data)
+            .await
+            .unwrap();
+        loaders
+            .load("counting", &[], &[], &key, &data)
+            .await
+            .unwrap();
+
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,3 @@
+mod loader;
+
+pub(crate) use loader::BatchLoaders;
@@ -0,0 +1,132 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+// Returns the files under `dir` whose path relative to `dir` matches
+// `pattern`, sorted so the SDL parts are always added in the same order.
+pub(crate) fn find_sdl_files(dir: &Path, pattern: &str) -> io::Result<Vec<PathBuf>> {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let mut files = Vec::new();
+    collect_files(dir, dir, &pattern, &mut files)?;
+    files.sort();
+    Ok(files)
+}
+
+fn collect_files(
+    root: &Path,
+    dir: &Path,
+    pattern: &[char],
+    files: &mut Vec<PathBuf>,
+) -> io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        // `file_type` does not follow symlinks, so a link back up the tree
+        // cannot make the walk recurse forever. Links to directories are
+        // skipped rather than read as files.
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            collect_files(root, &path, pattern, files)?;
+            continue;
+        }
+        if file_type.is_symlink() && path.is_dir() {
+            continue;
+        }
+
+        let relative = path.strip_prefix(root).unwrap_or(&path);
+        let relative: Vec<char> = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/")
+            .chars()
+            .collect();
+        if glob_match(pattern, &relative) {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+// `*` and `?` stay within one path segment, `**` spans directories and
+// `**/` also matches no directory at all.
+fn glob_match(pattern: &[char], path: &[char]) -> bool {
+    match pattern {
+        [] => path.is_empty(),
+        ['*', '*', '/', rest @ ..] => {
+            glob_match(rest, path)
+                || path
+                    .iter()
+                    .enumerate()
+                    .any(|(i, c)| *c == '/' && glob_match(rest, &path[i + 1..]))
+        }
+        ['*', '*', rest @ ..] => (0..=path.len()).any(|i| glob_match(rest, &path[i..])),
+        ['*', rest @ ..] => (0..=path.len())
+            .take_while(|&i| !path[..i].contains(&'/'))
+            .any(|i| glob_match(rest, &path[i..])),
+        ['?', rest @ ..] => {
+            matches!(path, [c, tail @ ..] if *c != '/' && glob_match(rest, tail))
+        }
+        [p, rest @ ..] => matches!(path, [c, tail @ ..] if c == p && glob_match(rest, tail)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(pattern: &str, path: &str) -> bool {
+        let pattern: Vec<char> = pattern.chars().collect();
+        let path: Vec<char> = path.chars().collect();
+        glob_match(&pattern, &path)
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(matches("*.graphql", "user.graphql"));
+        assert!(!matches("*.graphql", "users/user.graphql"));
+        assert!(!matches("*.graphql", "user.gql"));
+        assert!(matches("**/*.graphql", "user.graphql"));
+        assert!(matches("**/*.graphql", "users/types/user.graphql"));
+        assert!(matches("users/**", "users/types/user.graphql"));
+        assert!(matches("user?.graphql", "users.graphql"));
+        assert!(!matches("user?.graphql", "user.graphql"));
+    }
+
+    #[test]
+    fn test_find_sdl_files() {
+        let dir =
+            std::env::temp_dir().join(format!("graphql-resolver-files-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("users")).unwrap();
+        std::fs::write(dir.join("query.graphql"), "type Query").unwrap();
+        std::fs::write(dir.join("users/user.graphql"), "type User").unwrap();
+        std::fs::write(dir.join("notes.txt"), "").unwrap();
+
+        let files = find_sdl_files(&dir, "**/*.graphql").unwrap();
+        assert_eq!(
+            files,
+            [dir.join("query.graphql"), dir.join("users/user.graphql")]
+        );
+        let files = find_sdl_files(&dir, "*.graphql").unwrap();
+        assert_eq!(files, [dir.join("query.graphql")]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(find_sdl_files(&dir, "*.graphql").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_find_sdl_files_does_not_follow_directory_links() {
+        let dir =
+            std::env::temp_dir().join(format!("graphql-resolver-links-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("users")).unwrap();
+        std::fs::write(dir.join("users/user.graphql"), "type User").unwrap();
+        std::os::unix::fs::symlink(&dir, dir.join("users/root")).unwrap();
+
+        let files = find_sdl_files(&dir, "**/*.graphql").unwrap();
+        assert_eq!(files, [dir.join("users/user.graphql")]);
+        let files = find_sdl_files(&dir, "**").unwrap();
+        assert_eq!(files, [dir.join("users/user.graphql")]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
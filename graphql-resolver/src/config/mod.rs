@@ -1,9 +1,17 @@
+mod files;
 mod parser;
 mod schema;
+mod source;
 
+pub(crate) use files::find_sdl_files;
+#[cfg(test)]
 pub(crate) use parser::parse_sdl;
-#[allow(unused_imports)]
-pub(crate) use schema::ArgumentConfig;
+pub(crate) use parser::{parse_sdl_sources, ParseError};
+#[cfg(test)]
+pub(crate) use schema::EnumValueConfig;
 pub(crate) use schema::{
-    ArgumentMapping, FieldConfig, FieldType, GraphQLConfig, ResolverConfig, TypeConfig,
+    ArgumentConfig, ArgumentMapping, EnumConfig, FieldConfig, FieldType, GraphQLConfig,
+    InputObjectConfig, ObjectKind, ResolverConfig, ScalarConfig, TypeConfig, UnionConfig,
 };
+pub(crate) use source::SdlSources;
+pub use source::{SdlError, SourcePosition};
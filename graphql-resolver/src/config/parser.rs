@@ -2,45 +2,112 @@ use async_graphql_parser::types::{
     BaseType, ConstDirective, FieldDefinition, SchemaDefinition, ServiceDocument, Type,
     TypeDefinition, TypeKind, TypeSystemDefinition,
 };
+use async_graphql_parser::{Pos, Positioned};
 use async_graphql_value::ConstValue;
 
 use super::schema::{
-    ArgumentConfig, FieldConfig, FieldType, GraphQLConfig, ResolverConfig, TypeConfig,
+    ArgumentConfig, EnumConfig, EnumValueConfig, FieldConfig, FieldSource, FieldType,
+    GraphQLConfig, InputObjectConfig, ObjectKind, ResolverConfig, ScalarConfig, SourceMap,
+    TypeConfig, UnionConfig,
 };
+use super::source::{SdlError, SdlSources};
 use crate::directive::{
-    find_directive, parse_batch_key_directive, parse_call_directive, parse_resolver_directive,
+    find_directive, parse_batch_key_directive, parse_call_directive, parse_deprecated_directive,
+    parse_resolver_directive, parse_type_discriminator_directive,
 };
 
+const BUILTIN_SCALARS: [&str; 5] = ["Int", "Float", "String", "Boolean", "ID"];
+
 #[derive(Debug, thiserror::Error)]
 pub(crate) enum ParseError {
     #[error("Failed to parse SDL: {0}")]
-    SdlParseError(String),
+    SdlParseError(SdlError),
+    #[error("Conflicting SDL definitions")]
+    Conflict(Vec<SdlError>),
 }
 
+#[cfg(test)]
 pub(crate) fn parse_sdl(sdl: &str) -> Result<GraphQLConfig, ParseError> {
-    let document = async_graphql_parser::parse_schema(sdl)
-        .map_err(|e| ParseError::SdlParseError(e.to_string()))?;
+    parse_sdl_sources(SdlSources::from(sdl), false)
+}
+
+pub(crate) fn parse_sdl_sources(
+    sources: SdlSources,
+    merge_types: bool,
+) -> Result<GraphQLConfig, ParseError> {
+    let document = async_graphql_parser::parse_schema(sources.joined())
+        .map_err(|e| ParseError::SdlParseError(sdl_error(&e, &sources)))?;
+
+    build_config_from_document(document, sources, merge_types)
+}
 
-    build_config_from_document(document)
+// Syntax messages embed their own snippet of the joined document; only the
+// summary line is kept and the snippet is rendered against the original part.
+fn sdl_error(err: &async_graphql_parser::Error, sources: &SdlSources) -> SdlError {
+    let message = match err {
+        async_graphql_parser::Error::Syntax { message, .. } => message
+            .lines()
+            .find_map(|line| line.trim_start().strip_prefix("= "))
+            .unwrap_or(message)
+            .to_string(),
+        err => err.to_string(),
+    };
+    SdlError {
+        message,
+        position: err.positions().next().and_then(|pos| sources.locate(pos)),
+    }
 }
 
-fn build_config_from_document(document: ServiceDocument) -> Result<GraphQLConfig, ParseError> {
+fn build_config_from_document(
+    document: ServiceDocument,
+    sources: SdlSources,
+    merge_types: bool,
+) -> Result<GraphQLConfig, ParseError> {
     let mut config = GraphQLConfig::default();
+    config.source.set_sdl(sources);
+    let mut conflicts = Vec::new();
+    let mut extensions = Vec::new();
+    let mut schema_extensions = Vec::new();
 
     for definition in document.definitions {
         match definition {
+            TypeSystemDefinition::Schema(schema_def) if schema_def.node.extend => {
+                schema_extensions.push(schema_def);
+            }
             TypeSystemDefinition::Schema(schema_def) => {
                 process_schema_definition(&schema_def.node, &mut config);
             }
+            TypeSystemDefinition::Type(type_def) if type_def.node.extend => {
+                extensions.push(type_def);
+            }
             TypeSystemDefinition::Type(type_def) => {
-                if let Some(type_config) = process_type_definition(&type_def.node)? {
-                    config.types.insert(type_config.name.clone(), type_config);
-                }
+                process_type_definition(&type_def, &mut config, merge_types, &mut conflicts);
             }
             TypeSystemDefinition::Directive(_) => {}
         }
     }
 
+    // Extensions are applied once every part is processed, so an extension
+    // may come before the definition it extends.
+    for schema_def in &schema_extensions {
+        process_schema_extension(schema_def, &mut config, &mut conflicts);
+    }
+    for type_def in &extensions {
+        process_type_extension(type_def, &mut config, &mut conflicts);
+    }
+
+    if !conflicts.is_empty() {
+        return Err(ParseError::Conflict(
+            conflicts
+                .into_iter()
+                .map(|(pos, message)| SdlError {
+                    message,
+                    position: config.source.locate(pos),
+                })
+                .collect(),
+        ));
+    }
+
     infer_root_types(&mut config);
 
     Ok(config)
@@ -53,28 +120,313 @@ fn process_schema_definition(schema_def: &SchemaDefinition, config: &mut GraphQL
     if let Some(mutation) = &schema_def.mutation {
         config.mutation_type = Some(mutation.node.to_string());
     }
+    if let Some(subscription) = &schema_def.subscription {
+        config.subscription_type = Some(subscription.node.to_string());
+    }
 }
 
-fn process_type_definition(type_def: &TypeDefinition) -> Result<Option<TypeConfig>, ParseError> {
+fn process_schema_extension(
+    schema_def: &Positioned<SchemaDefinition>,
+    config: &mut GraphQLConfig,
+    conflicts: &mut Vec<(Pos, String)>,
+) {
+    let schema_def = &schema_def.node;
+    let operations = [
+        ("query", &schema_def.query, &mut config.query_type),
+        ("mutation", &schema_def.mutation, &mut config.mutation_type),
+        (
+            "subscription",
+            &schema_def.subscription,
+            &mut config.subscription_type,
+        ),
+    ];
+
+    for (operation, name, root_type) in operations {
+        let Some(name) = name else {
+            continue;
+        };
+        match root_type {
+            Some(existing) => conflicts.push((
+                name.pos,
+                format!(
+                    "cannot extend schema with {} type '{}' because it is already '{}'",
+                    operation, name.node, existing
+                ),
+            )),
+            None => *root_type = Some(name.node.to_string()),
+        }
+    }
+}
+
+fn process_type_definition(
+    type_def: &Positioned<TypeDefinition>,
+    config: &mut GraphQLConfig,
+    merge_types: bool,
+    conflicts: &mut Vec<(Pos, String)>,
+) {
+    let pos = type_def.pos;
+    let type_def = &type_def.node;
     let name = type_def.name.node.to_string();
+    let description = type_def.description.as_ref().map(|d| d.node.clone());
 
     if name.starts_with("__") {
-        return Ok(None);
+        return;
+    }
+
+    let mergeable = merge_types
+        && config
+            .types
+            .get(&name)
+            .is_some_and(|existing| same_kind(&type_def.kind, &existing.kind));
+    let is_builtin =
+        matches!(type_def.kind, TypeKind::Scalar) && BUILTIN_SCALARS.contains(&name.as_str());
+    if !mergeable && !is_builtin && is_defined(config, &name) {
+        conflicts.push((
+            pos,
+            format!(
+                "type '{}' is already defined at {}",
+                name,
+                describe_pos(&config.source, config.source.type_pos(&name))
+            ),
+        ));
+        return;
     }
 
-    let fields = match &type_def.kind {
-        TypeKind::Object(obj) => process_fields(&obj.fields),
-        TypeKind::Interface(iface) => process_fields(&iface.fields),
-        _ => return Ok(None),
+    match &type_def.kind {
+        TypeKind::Object(obj) => {
+            let implements = obj.implements.iter().map(|i| i.node.to_string()).collect();
+            let type_config = TypeConfig {
+                name: name.clone(),
+                description,
+                fields: Vec::new(),
+                kind: ObjectKind::Object,
+                implements,
+            };
+            add_type(config, pos, type_config);
+            add_fields(config, &name, &obj.fields, merge_types, conflicts);
+        }
+        TypeKind::Interface(iface) => {
+            let implements = iface
+                .implements
+                .iter()
+                .map(|i| i.node.to_string())
+                .collect();
+            let type_config = TypeConfig {
+                name: name.clone(),
+                description,
+                fields: Vec::new(),
+                kind: ObjectKind::Interface {
+                    discriminator: extract_type_discriminator(&type_def.directives),
+                },
+                implements,
+            };
+            add_type(config, pos, type_config);
+            add_fields(config, &name, &iface.fields, merge_types, conflicts);
+        }
+        TypeKind::Union(union_type) => {
+            let union_config = UnionConfig {
+                name,
+                description,
+                members: union_type
+                    .members
+                    .iter()
+                    .map(|m| m.node.to_string())
+                    .collect(),
+                discriminator: extract_type_discriminator(&type_def.directives),
+            };
+            config.source.insert_type(&union_config.name, pos);
+            config
+                .unions
+                .insert(union_config.name.clone(), union_config);
+        }
+        TypeKind::InputObject(input) => {
+            let input_config = InputObjectConfig {
+                name,
+                description,
+                fields: process_arguments(&input.fields),
+            };
+            config.source.insert_type(&input_config.name, pos);
+            config
+                .input_types
+                .insert(input_config.name.clone(), input_config);
+        }
+        TypeKind::Enum(enum_type) => {
+            let enum_config = EnumConfig {
+                name,
+                description,
+                values: enum_type
+                    .values
+                    .iter()
+                    .map(|v| EnumValueConfig {
+                        name: v.node.value.node.to_string(),
+                        description: v.node.description.as_ref().map(|d| d.node.clone()),
+                        deprecation: extract_deprecation(&v.node.directives),
+                    })
+                    .collect(),
+            };
+            config.source.insert_type(&enum_config.name, pos);
+            config.enums.insert(enum_config.name.clone(), enum_config);
+        }
+        TypeKind::Scalar if !is_builtin => {
+            let scalar_config = ScalarConfig { name, description };
+            config.source.insert_type(&scalar_config.name, pos);
+            config
+                .scalars
+                .insert(scalar_config.name.clone(), scalar_config);
+        }
+        _ => {}
+    }
+}
+
+fn process_type_extension(
+    type_def: &Positioned<TypeDefinition>,
+    config: &mut GraphQLConfig,
+    conflicts: &mut Vec<(Pos, String)>,
+) {
+    let pos = type_def.pos;
+    let type_def = &type_def.node;
+    let name = type_def.name.node.as_str();
+
+    let (keyword, implements, fields) = match &type_def.kind {
+        TypeKind::Object(obj) => ("type", &obj.implements, &obj.fields),
+        TypeKind::Interface(iface) => ("interface", &iface.implements, &iface.fields),
+        _ => {
+            conflicts.push((
+                pos,
+                format!(
+                    "'{}' cannot be extended; only object types and interfaces support 'extend'",
+                    name
+                ),
+            ));
+            return;
+        }
+    };
+
+    let Some(existing) = config
+        .types
+        .get_mut(name)
+        .filter(|existing| same_kind(&type_def.kind, &existing.kind))
+    else {
+        conflicts.push((
+            pos,
+            format!(
+                "cannot extend {} '{}' because no {} with that name is defined",
+                keyword, name, keyword
+            ),
+        ));
+        return;
+    };
+    for interface in implements {
+        if !existing.implements.iter().any(|i| *i == interface.node) {
+            existing.implements.push(interface.node.to_string());
+        }
+    }
+
+    add_fields(config, name, fields, false, conflicts);
+}
+
+fn same_kind(kind: &TypeKind, existing: &ObjectKind) -> bool {
+    matches!(
+        (kind, existing),
+        (TypeKind::Object(_), ObjectKind::Object)
+            | (TypeKind::Interface(_), ObjectKind::Interface { .. })
+    )
+}
+
+fn is_defined(config: &GraphQLConfig, name: &str) -> bool {
+    config.types.contains_key(name)
+        || config.unions.contains_key(name)
+        || config.input_types.contains_key(name)
+        || config.enums.contains_key(name)
+        || config.scalars.contains_key(name)
+}
+
+fn add_type(config: &mut GraphQLConfig, pos: Pos, type_config: TypeConfig) {
+    match config.types.get_mut(&type_config.name) {
+        Some(existing) => {
+            if existing.description.is_none() {
+                existing.description = type_config.description;
+            }
+            for interface in type_config.implements {
+                if !existing.implements.contains(&interface) {
+                    existing.implements.push(interface);
+                }
+            }
+        }
+        None => {
+            config.source.insert_type(&type_config.name, pos);
+            config.types.insert(type_config.name.clone(), type_config);
+        }
+    }
+}
+
+// A field that is already defined is a conflict, unless types are merged and
+// both definitions agree on the type, arguments and resolver.
+fn add_fields(
+    config: &mut GraphQLConfig,
+    type_name: &str,
+    fields: &[Positioned<FieldDefinition>],
+    merge_types: bool,
+    conflicts: &mut Vec<(Pos, String)>,
+) {
+    let Some(type_config) = config.types.get_mut(type_name) else {
+        return;
     };
 
-    Ok(Some(TypeConfig { name, fields }))
+    for f in fields {
+        let field = process_field(&f.node);
+        let Some(existing) = type_config.fields.iter().find(|e| e.name == field.name) else {
+            let field_source = FieldSource {
+                pos: f.pos,
+                batch_key: directive_pos(&f.node.directives, "batchKey"),
+                call: directive_pos(&f.node.directives, "call"),
+            };
+            config
+                .source
+                .insert_field(type_name, &field.name, field_source);
+            type_config.fields.push(field);
+            continue;
+        };
+
+        let first = describe_pos(
+            &config.source,
+            config.source.field(type_name, &field.name).pos,
+        );
+        if !merge_types {
+            conflicts.push((
+                f.pos,
+                format!(
+                    "field '{}.{}' is already defined at {}",
+                    type_name, field.name, first
+                ),
+            ));
+        } else if existing.field_type != field.field_type
+            || existing.arguments != field.arguments
+            || existing.resolver != field.resolver
+        {
+            conflicts.push((
+                f.pos,
+                format!(
+                    "field '{}.{}' does not match its definition at {}",
+                    type_name, field.name, first
+                ),
+            ));
+        }
+    }
+}
+
+fn describe_pos(source: &SourceMap, pos: Pos) -> String {
+    source
+        .locate(pos)
+        .map(|position| position.to_string())
+        .unwrap_or_else(|| format!("{}:{}", pos.line, pos.column))
 }
 
-fn process_fields(
-    fields: &[async_graphql_parser::Positioned<FieldDefinition>],
-) -> Vec<FieldConfig> {
-    fields.iter().map(|f| process_field(&f.node)).collect()
+fn directive_pos(directives: &[Positioned<ConstDirective>], name: &str) -> Option<Pos> {
+    directives
+        .iter()
+        .find(|d| d.node.name.node.as_str() == name)
+        .map(|d| d.pos)
 }
 
 fn process_field(field: &FieldDefinition) -> FieldConfig {
@@ -88,6 +440,8 @@ fn process_field(field: &FieldDefinition) -> FieldConfig {
         field_type,
         arguments,
         resolver,
+        description: field.description.as_ref().map(|d| d.node.clone()),
+        deprecation: extract_deprecation(&field.directives),
     }
 }
 
@@ -125,6 +479,8 @@ fn process_arguments(
                 name,
                 arg_type,
                 default_value,
+                description: a.node.description.as_ref().map(|d| d.node.clone()),
+                deprecation: extract_deprecation(&a.node.directives),
             }
         })
         .collect()
@@ -158,6 +514,22 @@ fn extract_resolver(
     None
 }
 
+fn extract_deprecation(
+    directives: &[async_graphql_parser::Positioned<ConstDirective>],
+) -> Option<String> {
+    find_directive(directives, "deprecated")
+        .and_then(parse_deprecated_directive)
+        .map(|d| d.reason)
+}
+
+fn extract_type_discriminator(
+    directives: &[async_graphql_parser::Positioned<ConstDirective>],
+) -> Option<String> {
+    find_directive(directives, "typeDiscriminator")
+        .and_then(parse_type_discriminator_directive)
+        .map(|d| d.field)
+}
+
 fn const_value_to_json(value: &ConstValue) -> serde_json::Value {
     match value {
         ConstValue::Null => serde_json::Value::Null,
@@ -202,6 +574,9 @@ fn infer_root_types(config: &mut GraphQLConfig) {
     if config.mutation_type.is_none() && config.types.contains_key("Mutation") {
         config.mutation_type = Some("Mutation".to_string());
     }
+    if config.subscription_type.is_none() && config.types.contains_key("Subscription") {
+        config.subscription_type = Some("Subscription".to_string());
+    }
 }
 
 #[cfg(test)]
@@ -227,7 +602,7 @@ mod tests {
         let result = parse_sdl(sdl);
         assert!(result.is_err());
         match result {
-            Err(ParseError::SdlParseError(msg)) => assert!(!msg.is_empty()),
+            Err(ParseError::SdlParseError(err)) => assert!(!err.message.is_empty()),
             _ => panic!("Expected SdlParseError"),
         }
     }
@@ -271,6 +646,43 @@ mod tests {
         assert_eq!(config.mutation_type, Some("Mutation".to_string()));
     }
 
+    #[test]
+    fn test_parse_with_subscription_type() {
+        let sdl = r#"
+            type Query {
+                hello: String
+            }
+
+            type Subscription {
+                ticks: Int @resolver(name: "ticks")
+            }
+        "#;
+
+        let config = parse_sdl(sdl).unwrap();
+        assert_eq!(config.subscription_type, Some("Subscription".to_string()));
+    }
+
+    #[test]
+    fn test_parse_with_explicit_subscription_type() {
+        let sdl = r#"
+            schema {
+                query: MyQuery
+                subscription: MySubscription
+            }
+
+            type MyQuery {
+                hello: String
+            }
+
+            type MySubscription {
+                ticks: Int
+            }
+        "#;
+
+        let config = parse_sdl(sdl).unwrap();
+        assert_eq!(config.subscription_type, Some("MySubscription".to_string()));
+    }
+
     #[test]
     fn test_parse_ignores_internal_types() {
         let sdl = r#"
@@ -304,10 +716,135 @@ mod tests {
         let node_type = config.types.get("Node").unwrap();
         assert_eq!(node_type.fields.len(), 1);
         assert_eq!(node_type.fields[0].name, "id");
+        assert!(matches!(
+            node_type.kind,
+            ObjectKind::Interface {
+                discriminator: None
+            }
+        ));
     }
 
     #[test]
-    fn test_parse_enum_type_skipped() {
+    fn test_parse_implements() {
+        let sdl = r#"
+            type Query {
+                node: Node
+            }
+
+            interface Node @typeDiscriminator(field: "kind") {
+                id: ID!
+            }
+
+            type User implements Node {
+                id: ID!
+                name: String
+            }
+        "#;
+
+        let config = parse_sdl(sdl).unwrap();
+        let user_type = config.types.get("User").unwrap();
+        assert!(matches!(user_type.kind, ObjectKind::Object));
+        assert_eq!(user_type.implements, vec!["Node".to_string()]);
+        assert_eq!(config.type_discriminator("Node"), Some("kind"));
+        assert_eq!(config.type_discriminator("User"), None);
+        assert_eq!(config.possible_types("Node"), vec!["User"]);
+    }
+
+    #[test]
+    fn test_parse_descriptions_and_deprecations() {
+        let sdl = r#"
+            "Root query"
+            type Query {
+                "Look up a user"
+                user("The user id" id: ID!, legacy: Boolean @deprecated(reason: "Unused")): User
+                oldUser: User @deprecated
+            }
+
+            "Account role"
+            enum Role {
+                "Full access"
+                ADMIN
+                GUEST @deprecated(reason: "Use MEMBER")
+            }
+
+            type User {
+                id: ID!
+            }
+        "#;
+
+        let config = parse_sdl(sdl).unwrap();
+        let query = config.types.get("Query").unwrap();
+        assert_eq!(query.description.as_deref(), Some("Root query"));
+
+        let user = &query.fields[0];
+        assert_eq!(user.description.as_deref(), Some("Look up a user"));
+        assert_eq!(user.deprecation, None);
+        assert_eq!(
+            user.arguments[0].description.as_deref(),
+            Some("The user id")
+        );
+        assert_eq!(user.arguments[1].deprecation.as_deref(), Some("Unused"));
+
+        let old_user = &query.fields[1];
+        assert_eq!(old_user.deprecation.as_deref(), Some("No longer supported"));
+
+        let role = config.enums.get("Role").unwrap();
+        assert_eq!(role.description.as_deref(), Some("Account role"));
+        assert_eq!(role.values[0].description.as_deref(), Some("Full access"));
+        assert_eq!(role.values[1].deprecation.as_deref(), Some("Use MEMBER"));
+    }
+
+    #[test]
+    fn test_parse_scalar_type() {
+        let sdl = r#"
+            scalar DateTime
+            scalar String
+
+            type Query {
+                now: DateTime!
+            }
+        "#;
+
+        let config = parse_sdl(sdl).unwrap();
+        assert!(config.scalars.contains_key("DateTime"));
+        assert!(!config.scalars.contains_key("String"));
+        assert!(!config.types.contains_key("DateTime"));
+    }
+
+    #[test]
+    fn test_parse_union_type() {
+        let sdl = r#"
+            type Query {
+                search: [SearchResult!]!
+            }
+
+            union SearchResult = User | Post
+
+            type User {
+                name: String
+            }
+
+            type Post {
+                title: String
+            }
+        "#;
+
+        let config = parse_sdl(sdl).unwrap();
+        assert!(!config.types.contains_key("SearchResult"));
+        let union_config = config.unions.get("SearchResult").unwrap();
+        assert_eq!(
+            union_config.members,
+            vec!["User".to_string(), "Post".to_string()]
+        );
+        assert_eq!(
+            config.type_discriminator("SearchResult"),
+            Some("__typename")
+        );
+        assert_eq!(config.possible_types("SearchResult"), vec!["User", "Post"]);
+    }
+
+    #[test]
+    fn test_parse_enum_type() {
         let sdl = r#"
             type Query {
                 status: Status
@@ -321,6 +858,48 @@ mod tests {
 
         let config = parse_sdl(sdl).unwrap();
         assert!(!config.types.contains_key("Status"));
+        let status = config.enums.get("Status").unwrap();
+        let values: Vec<_> = status.values.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(values, vec!["ACTIVE", "INACTIVE"]);
+    }
+
+    #[test]
+    fn test_parse_input_object_type() {
+        let sdl = r#"
+            type Query {
+                users(filter: UserFilter): [User!]!
+            }
+
+            type User {
+                id: ID!
+            }
+
+            input UserFilter {
+                name: String
+                limit: Int = 20
+                role: Role = MEMBER
+            }
+
+            enum Role {
+                ADMIN
+                MEMBER
+            }
+        "#;
+
+        let config = parse_sdl(sdl).unwrap();
+        assert!(!config.types.contains_key("UserFilter"));
+        let filter = config.input_types.get("UserFilter").unwrap();
+        assert_eq!(filter.fields.len(), 3);
+        assert_eq!(filter.fields[0].name, "name");
+        assert!(filter.fields[0].default_value.is_none());
+        assert_eq!(
+            filter.fields[1].default_value.as_ref().unwrap(),
+            &serde_json::json!(20)
+        );
+        assert_eq!(
+            filter.fields[2].default_value.as_ref().unwrap(),
+            &serde_json::json!("MEMBER")
+        );
     }
 
     #[test]
@@ -453,6 +1032,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_records_field_positions() {
+        let sdl = "type Query {\n  user: User\n  posts: [Post!]! @resolver(name: \"getPosts\") @batchKey(field: \"id\")\n}";
+
+        let config = parse_sdl(sdl).unwrap();
+        let user = config.source.field("Query", "user");
+        assert_eq!((user.pos.line, user.pos.column), (2, 3));
+        assert!(user.batch_key.is_none());
+
+        let posts = config.source.field("Query", "posts");
+        assert_eq!((posts.pos.line, posts.pos.column), (3, 3));
+        let batch_key = posts.batch_key.unwrap();
+        assert_eq!((batch_key.line, batch_key.column), (3, 47));
+        assert!(posts.call.is_none());
+    }
+
     #[test]
     fn test_parse_call_directive() {
         let sdl = r#"
@@ -497,15 +1092,243 @@ mod tests {
         assert!(config.types.contains_key("Query"));
     }
 
+    fn conflict_messages(result: Result<GraphQLConfig, ParseError>) -> Vec<String> {
+        match result {
+            Err(ParseError::Conflict(errors)) => errors.into_iter().map(|e| e.message).collect(),
+            _ => panic!("Expected Conflict error"),
+        }
+    }
+
+    #[test]
+    fn test_parse_type_extensions() {
+        let sdl = r#"
+            extend type Query {
+                posts: [Post!]!
+            }
+
+            type Query {
+                user: User
+            }
+
+            interface Node {
+                id: ID!
+            }
+
+            extend interface Node {
+                createdAt: String
+            }
+
+            type User {
+                id: ID!
+            }
+
+            extend type User implements Node {
+                createdAt: String
+            }
+
+            type Post {
+                id: ID!
+            }
+        "#;
+
+        let config = parse_sdl(sdl).unwrap();
+        let query = &config.types["Query"];
+        let fields: Vec<_> = query.fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(fields, ["user", "posts"]);
+        assert_eq!(config.source.field("Query", "posts").pos.line, 3);
+        assert_eq!(config.types["Node"].fields.len(), 2);
+        assert_eq!(config.types["User"].implements, ["Node"]);
+        assert_eq!(config.query_type.as_deref(), Some("Query"));
+    }
+
+    #[test]
+    fn test_parse_invalid_extensions() {
+        let sdl = r#"
+            type Query {
+                user: User
+            }
+
+            extend type Query {
+                user: User
+            }
+
+            extend type User {
+                id: ID!
+            }
+
+            extend interface Query {
+                id: ID!
+            }
+
+            union SearchResult = Query
+            extend union SearchResult = Query
+        "#;
+
+        assert_eq!(
+            conflict_messages(parse_sdl(sdl)),
+            [
+                "field 'Query.user' is already defined at <sdl>:3:17",
+                "cannot extend type 'User' because no type with that name is defined",
+                "cannot extend interface 'Query' because no interface with that name is defined",
+                "'SearchResult' cannot be extended; only object types and interfaces support 'extend'",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_schema_extension() {
+        let sdl = r#"
+            extend schema {
+                mutation: RootMutation
+            }
+
+            schema {
+                query: RootQuery
+            }
+
+            type RootQuery { hello: String }
+            type RootMutation { reset: Boolean }
+        "#;
+
+        let config = parse_sdl(sdl).unwrap();
+        assert_eq!(config.query_type.as_deref(), Some("RootQuery"));
+        assert_eq!(config.mutation_type.as_deref(), Some("RootMutation"));
+
+        let sdl = r#"
+            schema {
+                query: RootQuery
+            }
+
+            extend schema {
+                query: OtherQuery
+            }
+
+            type RootQuery { hello: String }
+            type OtherQuery { hello: String }
+        "#;
+
+        assert_eq!(
+            conflict_messages(parse_sdl(sdl)),
+            ["cannot extend schema with query type 'OtherQuery' because it is already 'RootQuery'"]
+        );
+    }
+
+    #[test]
+    fn test_parse_duplicate_types() {
+        let sdl = r#"
+            type Query {
+                user: User
+            }
+
+            type Query {
+                posts: [Post!]!
+            }
+
+            enum Role { ADMIN }
+            scalar Role
+        "#;
+
+        assert_eq!(
+            conflict_messages(parse_sdl(sdl)),
+            [
+                "type 'Query' is already defined at <sdl>:2:13",
+                "type 'Role' is already defined at <sdl>:10:13",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_merge_types() {
+        let mut sources = SdlSources::default();
+        sources.push(
+            "users.graphql",
+            "type Query {\n  user(id: ID!): User @resolver(name: \"getUser\")\n}\ntype User {\n  id: ID!\n}",
+        );
+        sources.push(
+            "posts.graphql",
+            "type Query {\n  user(id: ID!): User @resolver(name: \"getUser\")\n  posts: [Post!]!\n}\ntype Post {\n  id: ID!\n}",
+        );
+
+        let config = parse_sdl_sources(sources.clone(), true).unwrap();
+        let fields: Vec<_> = config.types["Query"]
+            .fields
+            .iter()
+            .map(|f| f.name.as_str())
+            .collect();
+        assert_eq!(fields, ["user", "posts"]);
+        assert_eq!(
+            config.source.locate_field("Query", "user").unwrap().source,
+            "users.graphql"
+        );
+
+        sources.push(
+            "admin.graphql",
+            "type Query {\n  user(id: ID): User @resolver(name: \"getUser\")\n}\ninterface Post {\n  id: ID!\n}",
+        );
+        let err = match parse_sdl_sources(sources, true) {
+            Err(ParseError::Conflict(errors)) => errors,
+            _ => panic!("Expected Conflict error"),
+        };
+        let messages: Vec<_> = err.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "field 'Query.user' does not match its definition at users.graphql:2:3",
+                "type 'Post' is already defined at posts.graphql:5:1",
+            ]
+        );
+        assert_eq!(
+            err[0].position.as_ref().unwrap().to_string(),
+            "admin.graphql:2:3"
+        );
+    }
+
+    #[test]
+    fn test_parse_error_position() {
+        let mut sources = SdlSources::default();
+        sources.push("query.graphql", "type Query {\n  user: User\n}");
+        sources.push("user.graphql", "type User {\n  id: ID!\n  name String\n}");
+
+        let err = match parse_sdl_sources(sources, false) {
+            Err(ParseError::SdlParseError(err)) => err,
+            _ => panic!("Expected SdlParseError"),
+        };
+        let position = err.position.unwrap();
+        assert_eq!(position.source, "user.graphql");
+        assert_eq!((position.line, position.column), (3, 8));
+        assert!(!err.message.contains("-->"));
+    }
+
+    #[test]
+    fn test_parse_error_position_at_end_of_part() {
+        let mut sources = SdlSources::default();
+        sources.push("a.graphql", "type A {\n  id: ID\n}\n");
+        sources.push("b.graphql", "type Query {\n");
+
+        let err = match parse_sdl_sources(sources, false) {
+            Err(ParseError::SdlParseError(err)) => err,
+            _ => panic!("Expected SdlParseError"),
+        };
+        let position = err.position.unwrap();
+        assert_eq!(position.to_string(), "b.graphql:2:1");
+        assert_eq!(position.line_text, "");
+    }
+
     #[test]
     fn test_parse_error_display() {
-        let err = ParseError::SdlParseError("test error".to_string());
+        let err = ParseError::SdlParseError(SdlError {
+            message: "test error".to_string(),
+            position: None,
+        });
         assert_eq!(err.to_string(), "Failed to parse SDL: test error");
     }
 
     #[test]
     fn test_parse_error_debug() {
-        let err = ParseError::SdlParseError("test".to_string());
+        let err = ParseError::SdlParseError(SdlError {
+            message: "test".to_string(),
+            position: None,
+        });
         let debug = format!("{:?}", err);
         assert!(debug.contains("SdlParseError"));
     }
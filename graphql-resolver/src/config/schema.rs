@@ -1,16 +1,156 @@
+use async_graphql_parser::Pos;
 use rustc_hash::FxHashMap;
 
+use super::source::{SdlSources, SourcePosition};
+
 #[derive(Debug, Clone, Default)]
 pub(crate) struct GraphQLConfig {
     pub types: FxHashMap<String, TypeConfig>,
+    pub input_types: FxHashMap<String, InputObjectConfig>,
+    pub enums: FxHashMap<String, EnumConfig>,
+    pub unions: FxHashMap<String, UnionConfig>,
+    pub scalars: FxHashMap<String, ScalarConfig>,
     pub query_type: Option<String>,
     pub mutation_type: Option<String>,
+    pub subscription_type: Option<String>,
+    pub source: SourceMap,
 }
 
-#[derive(Debug, Clone)]
+impl GraphQLConfig {
+    pub fn type_discriminator(&self, type_name: &str) -> Option<&str> {
+        if let Some(union_config) = self.unions.get(type_name) {
+            return Some(
+                union_config
+                    .discriminator
+                    .as_deref()
+                    .unwrap_or(DEFAULT_TYPE_DISCRIMINATOR),
+            );
+        }
+
+        match self.types.get(type_name).map(|t| &t.kind) {
+            Some(ObjectKind::Interface { discriminator }) => Some(
+                discriminator
+                    .as_deref()
+                    .unwrap_or(DEFAULT_TYPE_DISCRIMINATOR),
+            ),
+            _ => None,
+        }
+    }
+
+    pub fn possible_types(&self, type_name: &str) -> Vec<&str> {
+        if let Some(union_config) = self.unions.get(type_name) {
+            return union_config.members.iter().map(String::as_str).collect();
+        }
+
+        self.types
+            .values()
+            .filter(|t| t.implements.iter().any(|i| i == type_name))
+            .map(|t| t.name.as_str())
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SourceMap {
+    types: FxHashMap<String, Pos>,
+    fields: FxHashMap<(String, String), FieldSource>,
+    sdl: SdlSources,
+}
+
+impl SourceMap {
+    pub fn set_sdl(&mut self, sdl: SdlSources) {
+        self.sdl = sdl;
+    }
+
+    pub fn locate(&self, pos: Pos) -> Option<SourcePosition> {
+        self.sdl.locate(pos)
+    }
+
+    pub fn locate_field(&self, type_name: &str, field_name: &str) -> Option<SourcePosition> {
+        self.locate(self.field(type_name, field_name).pos)
+    }
+
+    pub fn insert_type(&mut self, type_name: &str, pos: Pos) {
+        self.types.insert(type_name.to_string(), pos);
+    }
+
+    pub fn type_pos(&self, type_name: &str) -> Pos {
+        self.types.get(type_name).copied().unwrap_or_default()
+    }
+
+    pub fn insert_field(&mut self, type_name: &str, field_name: &str, source: FieldSource) {
+        self.fields
+            .insert((type_name.to_string(), field_name.to_string()), source);
+    }
+
+    pub fn field(&self, type_name: &str, field_name: &str) -> FieldSource {
+        self.fields
+            .get(&(type_name.to_string(), field_name.to_string()))
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct FieldSource {
+    pub pos: Pos,
+    pub batch_key: Option<Pos>,
+    pub call: Option<Pos>,
+}
+
+pub(crate) const DEFAULT_TYPE_DISCRIMINATOR: &str = "__typename";
+
+#[derive(Debug, Clone, Default)]
 pub(crate) struct TypeConfig {
     pub name: String,
+    pub description: Option<String>,
     pub fields: Vec<FieldConfig>,
+    pub kind: ObjectKind,
+    pub implements: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) enum ObjectKind {
+    #[default]
+    Object,
+    Interface {
+        discriminator: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct UnionConfig {
+    pub name: String,
+    pub description: Option<String>,
+    pub members: Vec<String>,
+    pub discriminator: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct InputObjectConfig {
+    pub name: String,
+    pub description: Option<String>,
+    pub fields: Vec<ArgumentConfig>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ScalarConfig {
+    pub name: String,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct EnumConfig {
+    pub name: String,
+    pub description: Option<String>,
+    pub values: Vec<EnumValueConfig>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct EnumValueConfig {
+    pub name: String,
+    pub description: Option<String>,
+    pub deprecation: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -19,9 +159,11 @@ pub(crate) struct FieldConfig {
     pub field_type: FieldType,
     pub arguments: Vec<ArgumentConfig>,
     pub resolver: Option<ResolverConfig>,
+    pub description: Option<String>,
+    pub deprecation: Option<String>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub(crate) enum FieldType {
     Named(String),
     List(Box<FieldType>),
@@ -46,15 +188,16 @@ impl FieldType {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub(crate) struct ArgumentConfig {
     pub name: String,
     pub arg_type: FieldType,
-    #[allow(dead_code)]
     pub default_value: Option<serde_json::Value>,
+    pub description: Option<String>,
+    pub deprecation: Option<String>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub(crate) enum ResolverConfig {
     Trait {
         name: String,
@@ -82,7 +225,7 @@ impl ResolverConfig {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub(crate) enum ArgumentMapping {
     ParentField(String),
     Literal(serde_json::Value),
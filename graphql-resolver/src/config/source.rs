@@ -0,0 +1,192 @@
+use async_graphql_parser::Pos;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourcePosition {
+    pub source: String,
+    pub line: usize,
+    pub column: usize,
+    pub line_text: String,
+}
+
+impl SourcePosition {
+    pub fn snippet(&self) -> String {
+        let gutter = " ".repeat(self.line.to_string().len());
+        format!(
+            "{gutter}--> {self}\n{gutter} |\n{} | {}\n{gutter} | {}^",
+            self.line,
+            self.line_text,
+            " ".repeat(self.column.saturating_sub(1)),
+        )
+    }
+}
+
+impl std::fmt::Display for SourcePosition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}:{}", self.source, self.line, self.column)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SdlError {
+    pub message: String,
+    pub position: Option<SourcePosition>,
+}
+
+impl std::fmt::Display for SdlError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)?;
+        if let Some(position) = &self.position {
+            write!(f, "\n{}", position.snippet())?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for SdlError {}
+
+#[derive(Debug, Clone)]
+struct SdlPart {
+    name: String,
+    content: String,
+    first_line: usize,
+}
+
+// The SDL parts are parsed as one document joined by newlines, so a parser
+// position is mapped back to its part by the line the part starts at.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SdlSources {
+    parts: Vec<SdlPart>,
+}
+
+impl SdlSources {
+    pub fn push(&mut self, name: impl Into<String>, content: impl Into<String>) {
+        let first_line = self
+            .parts
+            .last()
+            .map(|part| part.first_line + part.content.matches('\n').count() + 1)
+            .unwrap_or(1);
+        self.parts.push(SdlPart {
+            name: name.into(),
+            content: content.into(),
+            first_line,
+        });
+    }
+
+    pub fn len(&self) -> usize {
+        self.parts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parts.is_empty()
+    }
+
+    pub fn joined(&self) -> String {
+        self.parts
+            .iter()
+            .map(|part| part.content.as_str())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    pub fn locate(&self, pos: Pos) -> Option<SourcePosition> {
+        if pos.line == 0 {
+            return None;
+        }
+        let part = self
+            .parts
+            .iter()
+            .rev()
+            .find(|part| part.first_line <= pos.line)?;
+        let line = pos.line - part.first_line + 1;
+        if line > part.content.matches('\n').count() + 1 {
+            return None;
+        }
+        // `lines` yields nothing for the empty line after a trailing newline.
+        let line_text = part.content.lines().nth(line - 1).unwrap_or_default();
+        Some(SourcePosition {
+            source: part.name.clone(),
+            line,
+            column: pos.column,
+            line_text: line_text.to_string(),
+        })
+    }
+}
+
+impl From<&str> for SdlSources {
+    fn from(sdl: &str) -> Self {
+        let mut sources = Self::default();
+        sources.push("<sdl>", sdl);
+        sources
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_sources() -> SdlSources {
+        let mut sources = SdlSources::default();
+        sources.push("query.graphql", "type Query {\n  user: User\n}");
+        sources.push("user.graphql", "type User {\n  id: ID!\n}");
+        sources
+    }
+
+    #[test]
+    fn test_locate_maps_joined_lines_to_parts() {
+        let sources = make_sources();
+        assert_eq!(sources.len(), 2);
+
+        let position = sources.locate(Pos { line: 2, column: 3 }).unwrap();
+        assert_eq!(position.source, "query.graphql");
+        assert_eq!(position.line, 2);
+        assert_eq!(position.line_text, "  user: User");
+
+        let position = sources.locate(Pos { line: 5, column: 3 }).unwrap();
+        assert_eq!(position.source, "user.graphql");
+        assert_eq!(position.line, 2);
+        assert_eq!(position.line_text, "  id: ID!");
+        assert_eq!(position.to_string(), "user.graphql:2:3");
+    }
+
+    #[test]
+    fn test_locate_empty_last_line() {
+        let mut sources = SdlSources::default();
+        sources.push("a.graphql", "type A {\n  id: ID\n}\n");
+        sources.push("b.graphql", "type Query {\n");
+
+        let position = sources.locate(Pos { line: 4, column: 1 }).unwrap();
+        assert_eq!(position.source, "a.graphql");
+        assert_eq!(position.line, 4);
+        assert_eq!(position.line_text, "");
+
+        let position = sources.locate(Pos { line: 6, column: 1 }).unwrap();
+        assert_eq!(position.to_string(), "b.graphql:2:1");
+        assert_eq!(position.line_text, "");
+    }
+
+    #[test]
+    fn test_locate_unknown_position() {
+        let sources = make_sources();
+        assert!(sources.locate(Pos::default()).is_none());
+        assert!(sources
+            .locate(Pos {
+                line: 42,
+                column: 1
+            })
+            .is_none());
+    }
+
+    #[test]
+    fn test_snippet_points_at_column() {
+        let position = SourcePosition {
+            source: "user.graphql".to_string(),
+            line: 12,
+            column: 3,
+            line_text: "  id: ID".to_string(),
+        };
+        assert_eq!(
+            position.snippet(),
+            "  --> user.graphql:12:3\n   |\n12 |   id: ID\n   |   ^"
+        );
+    }
+}
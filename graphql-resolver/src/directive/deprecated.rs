@@ -0,0 +1,22 @@
+use async_graphql_parser::types::ConstDirective;
+
+use super::get_string_argument;
+
+const DEFAULT_DEPRECATION_REASON: &str = "No longer supported";
+
+#[derive(Debug, Clone)]
+pub(crate) struct DeprecatedDirective {
+    pub reason: String,
+}
+
+pub(crate) fn parse_deprecated_directive(
+    directive: &ConstDirective,
+) -> Option<DeprecatedDirective> {
+    if directive.name.node.as_str() != "deprecated" {
+        return None;
+    }
+
+    let reason = get_string_argument(directive, "reason")
+        .unwrap_or_else(|| DEFAULT_DEPRECATION_REASON.to_string());
+    Some(DeprecatedDirective { reason })
+}
@@ -1,10 +1,14 @@
 pub(crate) mod batch_key;
 pub(crate) mod call_directive;
+pub(crate) mod deprecated;
 pub(crate) mod resolver_directive;
+pub(crate) mod type_discriminator;
 
 pub(crate) use batch_key::parse_batch_key_directive;
 pub(crate) use call_directive::parse_call_directive;
+pub(crate) use deprecated::parse_deprecated_directive;
 pub(crate) use resolver_directive::parse_resolver_directive;
+pub(crate) use type_discriminator::parse_type_discriminator_directive;
 
 use async_graphql_parser::types::ConstDirective;
 use async_graphql_value::ConstValue;
@@ -0,0 +1,19 @@
+use async_graphql_parser::types::ConstDirective;
+
+use super::get_string_argument;
+
+#[derive(Debug, Clone)]
+pub(crate) struct TypeDiscriminatorDirective {
+    pub field: String,
+}
+
+pub(crate) fn parse_type_discriminator_directive(
+    directive: &ConstDirective,
+) -> Option<TypeDiscriminatorDirective> {
+    if directive.name.node.as_str() != "typeDiscriminator" {
+        return None;
+    }
+
+    let field = get_string_argument(directive, "field")?;
+    Some(TypeDiscriminatorDirective { field })
+}
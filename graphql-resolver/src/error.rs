@@ -1,3 +1,7 @@
+use std::sync::Arc;
+
+use async_graphql::{ErrorExtensionValues, Value};
+use rustc_hash::FxHashMap;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -10,6 +14,121 @@ pub enum ResolverError {
     Execution(String),
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
+    #[error("{}", .0.message)]
+    Custom(Box<ErrorDetails>),
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ErrorDetails {
+    pub message: String,
+    pub code: Option<String>,
+    pub extensions: FxHashMap<String, serde_json::Value>,
+    pub data: Option<Value>,
+}
+
+impl ResolverError {
+    pub fn new(message: impl Into<String>) -> Self {
+        ResolverError::Custom(Box::new(ErrorDetails {
+            message: message.into(),
+            ..ErrorDetails::default()
+        }))
+    }
+
+    pub fn with_code(self, code: impl Into<String>) -> Self {
+        let mut details = self.into_details();
+        details.code = Some(code.into());
+        ResolverError::Custom(details)
+    }
+
+    pub fn with_extension(
+        self,
+        key: impl Into<String>,
+        value: impl Into<serde_json::Value>,
+    ) -> Self {
+        let mut details = self.into_details();
+        details.extensions.insert(key.into(), value.into());
+        ResolverError::Custom(details)
+    }
+
+    pub fn with_data(self, data: Value) -> Self {
+        let mut details = self.into_details();
+        details.data = Some(data);
+        ResolverError::Custom(details)
+    }
+
+    pub fn code(&self) -> Option<&str> {
+        match self {
+            ResolverError::NotFound(_) => Some("RESOLVER_NOT_FOUND"),
+            ResolverError::Argument(_) => Some("BAD_USER_INPUT"),
+            ResolverError::Execution(_) => Some("EXECUTION_ERROR"),
+            ResolverError::Serialization(_) => Some("SERIALIZATION_ERROR"),
+            ResolverError::Custom(details) => details.code.as_deref(),
+        }
+    }
+
+    pub fn extensions(&self) -> Option<&FxHashMap<String, serde_json::Value>> {
+        match self {
+            ResolverError::Custom(details) => Some(&details.extensions),
+            _ => None,
+        }
+    }
+
+    pub fn data(&self) -> Option<&Value> {
+        match self {
+            ResolverError::Custom(details) => details.data.as_ref(),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn to_graphql_error(&self) -> async_graphql::Error {
+        let mut extensions = ErrorExtensionValues::default();
+        if let Some(code) = self.code() {
+            extensions.set("code", code);
+        }
+        for (key, value) in self.extensions().into_iter().flatten() {
+            extensions.set(key, Value::from_json(value.clone()).unwrap_or(Value::Null));
+        }
+
+        let mut error = async_graphql::Error::new(self.to_string());
+        error.extensions = Some(extensions);
+        error
+    }
+
+    fn into_details(self) -> Box<ErrorDetails> {
+        match self {
+            ResolverError::Custom(details) => details,
+            other => Box::new(ErrorDetails {
+                message: other.to_string(),
+                code: other.code().map(str::to_string),
+                ..ErrorDetails::default()
+            }),
+        }
+    }
+}
+
+type ErrorHook = dyn Fn(&ResolverError) -> Option<ResolverError> + Send + Sync;
+
+#[derive(Clone, Default)]
+pub(crate) struct ErrorMapper(Option<Arc<ErrorHook>>);
+
+impl ErrorMapper {
+    pub fn new<F>(hook: F) -> Self
+    where
+        F: Fn(&ResolverError) -> Option<ResolverError> + Send + Sync + 'static,
+    {
+        Self(Some(Arc::new(hook)))
+    }
+
+    pub fn map_error(&self, err: &ResolverError) -> Option<ResolverError> {
+        self.0.as_ref().and_then(|hook| hook(err))
+    }
+
+    pub fn to_graphql_error(&self, err: &ResolverError) -> async_graphql::Error {
+        match self.map_error(err) {
+            Some(mapped) => mapped.to_graphql_error(),
+            None => err.to_graphql_error(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -58,6 +177,56 @@ mod tests {
         assert!(debug.contains("NotFound"));
     }
 
+    #[test]
+    fn test_custom_error() {
+        let err = ResolverError::new("Not allowed")
+            .with_code("FORBIDDEN")
+            .with_extension("field", "email")
+            .with_data(Value::Null);
+        assert_eq!(err.to_string(), "Not allowed");
+        assert_eq!(err.code(), Some("FORBIDDEN"));
+        assert_eq!(
+            err.extensions().unwrap().get("field"),
+            Some(&serde_json::json!("email"))
+        );
+        assert_eq!(err.data(), Some(&Value::Null));
+    }
+
+    #[test]
+    fn test_builtin_errors_keep_message_and_code() {
+        let err = ResolverError::Execution("db down".to_string()).with_extension("retry", true);
+        assert_eq!(err.to_string(), "Execution error: db down");
+        assert_eq!(err.code(), Some("EXECUTION_ERROR"));
+        assert!(err.data().is_none());
+    }
+
+    #[test]
+    fn test_to_graphql_error_extensions() {
+        let err = ResolverError::new("Not allowed")
+            .with_code("FORBIDDEN")
+            .with_extension("limit", 5);
+        let gql = err.to_graphql_error();
+        assert_eq!(gql.message, "Not allowed");
+        let extensions = gql.extensions.unwrap();
+        assert_eq!(
+            extensions.get("code"),
+            Some(&Value::String("FORBIDDEN".to_string()))
+        );
+        assert_eq!(extensions.get("limit"), Some(&Value::Number(5.into())));
+    }
+
+    #[test]
+    fn test_error_mapper() {
+        let mapper = ErrorMapper::new(|err| match err {
+            ResolverError::Execution(_) => Some(ResolverError::new("Internal error")),
+            _ => None,
+        });
+        let redacted = mapper.to_graphql_error(&ResolverError::Execution("secret".to_string()));
+        assert_eq!(redacted.message, "Internal error");
+        let kept = mapper.to_graphql_error(&ResolverError::Argument("bad id".to_string()));
+        assert_eq!(kept.message, "Argument error: bad id");
+    }
+
     #[test]
     fn test_error_trait_impl() {
         let err = ResolverError::Execution("test".to_string());
@@ -1,20 +1,45 @@
+mod batch;
 mod config;
 mod directive;
 mod error;
 mod n1;
 mod registry;
+mod scalar;
 mod schema;
 mod server;
+mod validation;
 
-pub use error::ResolverError;
+pub use config::{SdlError, SourcePosition};
+pub use error::{ErrorDetails, ResolverError};
 pub use n1::N1Error;
-pub use registry::resolver::{BatchResolver, BoxFuture, Resolver, ResolverContext, ResolverResult};
+pub use registry::execute::{ExecuteResolver, LoadBatchResolver};
+pub use registry::resolver::{
+    BatchResolver, BoxFuture, BoxStream, Resolver, ResolverContext, ResolverResult,
+    SubscriptionResolver,
+};
+pub use registry::selection::SelectedField;
 pub use registry::storage::{
     BatchResolverRegistration, ErasedBatchResolver, ResolverRegistration, TraitRegistry,
 };
+#[cfg(feature = "bigint")]
+pub use scalar::BigIntCodec;
+#[cfg(feature = "datetime")]
+pub use scalar::DateTimeCodec;
+#[cfg(feature = "json")]
+pub use scalar::JsonCodec;
+pub use scalar::ScalarCodec;
+#[cfg(feature = "uuid")]
+pub use scalar::UuidCodec;
 pub use server::{GraphQLServer, GraphQLServerBuilder, ServerError, ValidatedServerBuilder};
+pub use validation::ValidationError;
 
 pub use inventory;
 pub use rustc_hash::FxHashMap;
 
-pub use graphql_resolver_derive::TraitResolver;
+pub use graphql_resolver_derive::{resolver, TraitResolver};
+
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::registry::execute::{deserialize_args, extract_arg, serialize_output};
+    pub use async_graphql::Value;
+}
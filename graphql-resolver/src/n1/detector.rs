@@ -20,9 +20,14 @@ impl<'a> N1Detector<'a> {
     }
 
     pub fn detect(mut self) -> Result<(), Vec<N1Error>> {
-        if let Some(query_type) = &self.config.query_type {
+        let root_types = [
+            self.config.query_type.clone(),
+            self.config.mutation_type.clone(),
+            self.config.subscription_type.clone(),
+        ];
+        for root_type in root_types.into_iter().flatten() {
             let mut visited = FxHashSet::default();
-            self.traverse(query_type, vec![query_type.clone()], &mut visited);
+            self.traverse(&root_type, vec![root_type.clone()], &mut visited);
         }
 
         if self.errors.is_empty() {
@@ -50,7 +55,10 @@ impl<'a> N1Detector<'a> {
 
             if let Some(inner_type) = field.field_type.inner_type_name() {
                 if self.config.types.contains_key(inner_type) {
-                    self.traverse(inner_type, field_path, visited);
+                    self.traverse(inner_type, field_path.clone(), visited);
+                }
+                for possible_type in self.config.possible_types(inner_type) {
+                    self.traverse(possible_type, field_path.clone(), visited);
                 }
             }
         }
@@ -77,6 +85,7 @@ impl<'a> N1Detector<'a> {
                      Add @batchKey directive or use a BatchResolver.",
                     field.name, parent_type
                 ),
+                position: self.config.source.locate_field(parent_type, &field.name),
             });
         }
     }
@@ -86,19 +95,14 @@ impl<'a> N1Detector<'a> {
             return false;
         }
 
-        let root_type = self.config.query_type.as_deref().unwrap_or("Query");
-        let mut current_type = root_type.to_string();
+        let mut current_type = path[0].clone();
 
         for (i, segment) in path.iter().enumerate().skip(1) {
             if i == path.len() - 1 {
                 break;
             }
 
-            let Some(type_config) = self.config.types.get(&current_type) else {
-                return false;
-            };
-
-            let Some(field_config) = type_config.fields.iter().find(|f| &f.name == segment) else {
+            let Some(field_config) = self.find_field(&current_type, segment) else {
                 return false;
             };
 
@@ -107,7 +111,9 @@ impl<'a> N1Detector<'a> {
             }
 
             if let Some(inner_type) = field_config.field_type.inner_type_name() {
-                if self.config.types.contains_key(inner_type) {
+                if self.config.types.contains_key(inner_type)
+                    || self.config.unions.contains_key(inner_type)
+                {
                     current_type = inner_type.to_string();
                 }
             }
@@ -115,6 +121,23 @@ impl<'a> N1Detector<'a> {
 
         false
     }
+
+    fn find_field(&self, type_name: &str, field_name: &str) -> Option<&'a FieldConfig> {
+        let config = self.config;
+        let find_in = |name: &str| {
+            config
+                .types
+                .get(name)
+                .and_then(|t| t.fields.iter().find(|f| f.name == field_name))
+        };
+
+        find_in(type_name).or_else(|| {
+            config
+                .possible_types(type_name)
+                .into_iter()
+                .find_map(find_in)
+        })
+    }
 }
 
 #[cfg(test)]
@@ -133,6 +156,7 @@ mod tests {
                 TypeConfig {
                     name: name.to_string(),
                     fields,
+                    ..Default::default()
                 },
             );
         }
@@ -149,6 +173,8 @@ mod tests {
             field_type,
             arguments: vec![],
             resolver,
+            description: None,
+            deprecation: None,
         }
     }
 
@@ -384,6 +410,49 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_detector_mutation_payload_list_without_batch_key() {
+        let mut config = make_config_with_types(vec![
+            (
+                "Query",
+                vec![make_field(
+                    "hello",
+                    FieldType::Named("String".to_string()),
+                    None,
+                )],
+            ),
+            (
+                "Mutation",
+                vec![make_field(
+                    "importUsers",
+                    FieldType::List(Box::new(FieldType::Named("User".to_string()))),
+                    Some(ResolverConfig::Trait {
+                        name: "importUsers".to_string(),
+                        batch_key: None,
+                    }),
+                )],
+            ),
+            (
+                "User",
+                vec![make_field(
+                    "posts",
+                    FieldType::List(Box::new(FieldType::Named("Post".to_string()))),
+                    Some(ResolverConfig::Trait {
+                        name: "getPosts".to_string(),
+                        batch_key: None,
+                    }),
+                )],
+            ),
+        ]);
+        config.mutation_type = Some("Mutation".to_string());
+        let registry = TraitRegistry::default();
+
+        let detector = N1Detector::new(&config, &registry);
+        let errors = detector.detect().unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, vec!["Mutation", "importUsers", "posts"]);
+    }
+
     #[test]
     fn test_detector_call_resolver() {
         let config = make_config_with_types(vec![
@@ -416,4 +485,91 @@ mod tests {
         let result = detector.detect();
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_detector_union_members_in_list_context() {
+        let mut config = make_config_with_types(vec![
+            (
+                "Query",
+                vec![make_field(
+                    "search",
+                    FieldType::List(Box::new(FieldType::Named("SearchResult".to_string()))),
+                    None,
+                )],
+            ),
+            (
+                "User",
+                vec![make_field(
+                    "posts",
+                    FieldType::List(Box::new(FieldType::Named("Post".to_string()))),
+                    Some(ResolverConfig::Trait {
+                        name: "getPosts".to_string(),
+                        batch_key: None,
+                    }),
+                )],
+            ),
+        ]);
+        config.unions.insert(
+            "SearchResult".to_string(),
+            crate::config::UnionConfig {
+                name: "SearchResult".to_string(),
+                description: None,
+                members: vec!["User".to_string()],
+                discriminator: None,
+            },
+        );
+        let registry = TraitRegistry::default();
+
+        let detector = N1Detector::new(&config, &registry);
+        let errors = detector.detect().unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, vec!["Query", "search", "posts"]);
+    }
+
+    #[test]
+    fn test_detector_interface_implementors() {
+        let mut config = make_config_with_types(vec![
+            (
+                "Query",
+                vec![make_field(
+                    "node",
+                    FieldType::Named("Node".to_string()),
+                    None,
+                )],
+            ),
+            (
+                "Node",
+                vec![make_field("id", FieldType::Named("ID".to_string()), None)],
+            ),
+            (
+                "User",
+                vec![make_field(
+                    "posts",
+                    FieldType::List(Box::new(FieldType::Named("Post".to_string()))),
+                    None,
+                )],
+            ),
+            (
+                "Post",
+                vec![make_field(
+                    "author",
+                    FieldType::Named("User".to_string()),
+                    Some(ResolverConfig::Trait {
+                        name: "getUser".to_string(),
+                        batch_key: None,
+                    }),
+                )],
+            ),
+        ]);
+        config.types.get_mut("Node").unwrap().kind = crate::config::ObjectKind::Interface {
+            discriminator: None,
+        };
+        config.types.get_mut("User").unwrap().implements = vec!["Node".to_string()];
+        let registry = TraitRegistry::default();
+
+        let detector = N1Detector::new(&config, &registry);
+        let errors = detector.detect().unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, vec!["Query", "node", "posts", "author"]);
+    }
 }
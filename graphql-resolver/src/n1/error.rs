@@ -1,9 +1,12 @@
+use crate::config::SourcePosition;
+
 #[derive(Debug, Clone)]
 pub struct N1Error {
     pub path: Vec<String>,
     pub field_name: String,
     pub parent_type: String,
     pub message: String,
+    pub position: Option<SourcePosition>,
 }
 
 impl std::fmt::Display for N1Error {
@@ -13,7 +16,11 @@ impl std::fmt::Display for N1Error {
             "N+1 detected at {}: {}",
             self.path.join("."),
             self.message
-        )
+        )?;
+        if let Some(position) = &self.position {
+            write!(f, "\n{}", position.snippet())?;
+        }
+        Ok(())
     }
 }
 
@@ -34,6 +41,7 @@ mod tests {
             field_name: "posts".to_string(),
             parent_type: "User".to_string(),
             message: "Field 'posts' causes N+1".to_string(),
+            position: None,
         };
 
         let display = format!("{}", error);
@@ -41,6 +49,28 @@ mod tests {
         assert!(display.contains("Field 'posts' causes N+1"));
     }
 
+    #[test]
+    fn test_n1_error_display_with_position() {
+        let error = N1Error {
+            path: vec!["Query".to_string(), "users".to_string()],
+            field_name: "posts".to_string(),
+            parent_type: "User".to_string(),
+            message: "Field 'posts' causes N+1".to_string(),
+            position: Some(SourcePosition {
+                source: "user.graphql".to_string(),
+                line: 3,
+                column: 5,
+                line_text: "    posts: [Post!]!".to_string(),
+            }),
+        };
+
+        assert_eq!(
+            error.to_string(),
+            "N+1 detected at Query.users: Field 'posts' causes N+1\n \
+             --> user.graphql:3:5\n  |\n3 |     posts: [Post!]!\n  |     ^"
+        );
+    }
+
     #[test]
     fn test_n1_error_empty_path() {
         let error = N1Error {
@@ -48,6 +78,7 @@ mod tests {
             field_name: "field".to_string(),
             parent_type: "Type".to_string(),
             message: "error".to_string(),
+            position: None,
         };
 
         let display = format!("{}", error);
@@ -61,6 +92,7 @@ mod tests {
             field_name: "field".to_string(),
             parent_type: "Type".to_string(),
             message: "msg".to_string(),
+            position: None,
         };
 
         let debug = format!("{:?}", error);
@@ -74,6 +106,7 @@ mod tests {
             field_name: "field".to_string(),
             parent_type: "Type".to_string(),
             message: "msg".to_string(),
+            position: None,
         };
 
         let cloned = error.clone();
@@ -88,6 +121,7 @@ mod tests {
             field_name: "f".to_string(),
             parent_type: "T".to_string(),
             message: "m".to_string(),
+            position: None,
         };
 
         let err: &dyn std::error::Error = &error;
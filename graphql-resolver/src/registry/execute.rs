@@ -0,0 +1,176 @@
+use std::hash::Hash;
+
+use async_graphql::{Name, Value};
+use rustc_hash::FxHashMap;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::error::ResolverError;
+use crate::registry::resolver::{BoxFuture, ResolverContext, ResolverResult};
+
+/// The method a `#[derive(TraitResolver)]` resolver implements.
+///
+/// The derived `Resolver::resolve` calls `execute` with the field arguments and
+/// serializes the returned `Output` into a GraphQL value. `Args` is the raw
+/// argument map unless the type is annotated with `#[resolver(args = MyArgs)]`,
+/// in which case the map is deserialized into `MyArgs` first and a mismatch is
+/// reported as `ResolverError::Argument`.
+pub trait ExecuteResolver<Args = FxHashMap<String, Value>>: Send + Sync + 'static {
+    type Output: Serialize;
+
+    fn execute<'a>(
+        &'a self,
+        ctx: &'a ResolverContext,
+        args: Args,
+    ) -> BoxFuture<'a, ResolverResult<Self::Output>>;
+}
+
+/// The typed loader a `#[derive(TraitResolver)]` batch resolver implements.
+///
+/// `#[batch_resolver(name = "..", batch_key = "..")]` derives `BatchResolver`
+/// from this trait, so the type can be passed to `register_batch_resolver`.
+/// Keys are deserialized from the parents' `@batchKey` values and loaded values
+/// are serialized back into the response.
+#[allow(clippy::type_complexity)]
+pub trait LoadBatchResolver: Send + Sync + 'static {
+    type Key: Clone + Eq + Hash + Serialize + DeserializeOwned + Send + Sync + 'static;
+    type Value: Clone + Serialize + Send + Sync + 'static;
+
+    fn load<'a>(
+        &'a self,
+        ctx: &'a ResolverContext,
+        keys: Vec<Self::Key>,
+    ) -> BoxFuture<'a, ResolverResult<Vec<(Self::Key, Self::Value)>>>;
+
+    fn load_each<'a>(
+        &'a self,
+        ctx: &'a ResolverContext,
+        keys: Vec<Self::Key>,
+    ) -> BoxFuture<'a, ResolverResult<Vec<(Self::Key, ResolverResult<Self::Value>)>>> {
+        Box::pin(async move {
+            let results = self.load(ctx, keys).await?;
+            Ok(results
+                .into_iter()
+                .map(|(key, value)| (key, Ok(value)))
+                .collect())
+        })
+    }
+
+    fn cache_results(&self) -> bool {
+        true
+    }
+}
+
+pub fn deserialize_args<T: DeserializeOwned>(
+    resolver_name: &str,
+    args: FxHashMap<String, Value>,
+) -> ResolverResult<T> {
+    let object = args
+        .into_iter()
+        .map(|(name, value)| (Name::new(name), value))
+        .collect();
+    async_graphql::from_value(Value::Object(object)).map_err(|e| {
+        ResolverError::Argument(format!(
+            "Invalid arguments for resolver '{}': {}",
+            resolver_name, e
+        ))
+    })
+}
+
+pub fn extract_arg<T: DeserializeOwned>(
+    resolver_name: &str,
+    args: &mut FxHashMap<String, Value>,
+    name: &str,
+) -> ResolverResult<T> {
+    let value = args.remove(name).unwrap_or(Value::Null);
+    async_graphql::from_value(value).map_err(|e| {
+        ResolverError::Argument(format!(
+            "Invalid argument '{}' for resolver '{}': {}",
+            name, resolver_name, e
+        ))
+    })
+}
+
+pub fn serialize_output<T: Serialize>(resolver_name: &str, output: T) -> ResolverResult<Value> {
+    async_graphql::to_value(output).map_err(|e| {
+        ResolverError::Execution(format!(
+            "Failed to serialize output of resolver '{}': {}",
+            resolver_name, e
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct Args {
+        id: String,
+        limit: Option<i32>,
+    }
+
+    #[test]
+    fn test_deserialize_args() {
+        let mut args = FxHashMap::default();
+        args.insert("id".to_string(), Value::String("1".to_string()));
+        let parsed: Args = deserialize_args("getUser", args).unwrap();
+        assert_eq!(
+            parsed,
+            Args {
+                id: "1".to_string(),
+                limit: None
+            }
+        );
+    }
+
+    #[test]
+    fn test_deserialize_args_error() {
+        let err = deserialize_args::<Args>("getUser", FxHashMap::default()).unwrap_err();
+        match err {
+            ResolverError::Argument(msg) => {
+                assert!(msg.starts_with("Invalid arguments for resolver 'getUser'"))
+            }
+            _ => panic!("Expected Argument error"),
+        }
+    }
+
+    #[test]
+    fn test_extract_arg() {
+        let mut args = FxHashMap::default();
+        args.insert("userId".to_string(), Value::String("1".to_string()));
+
+        let id: String = extract_arg("getUser", &mut args, "userId").unwrap();
+        assert_eq!(id, "1");
+        let limit: Option<i32> = extract_arg("getUser", &mut args, "limit").unwrap();
+        assert_eq!(limit, None);
+
+        let err = extract_arg::<String>("getUser", &mut args, "userId").unwrap_err();
+        match err {
+            ResolverError::Argument(msg) => {
+                assert!(msg.starts_with("Invalid argument 'userId' for resolver 'getUser'"))
+            }
+            _ => panic!("Expected Argument error"),
+        }
+    }
+
+    #[test]
+    fn test_serialize_output() {
+        #[derive(serde::Serialize)]
+        struct User {
+            id: String,
+        }
+
+        let value = serialize_output(
+            "getUser",
+            User {
+                id: "1".to_string(),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            value,
+            Value::from_json(serde_json::json!({"id": "1"})).unwrap()
+        );
+    }
+}
@@ -1,2 +1,4 @@
+pub(crate) mod execute;
 pub(crate) mod resolver;
+pub(crate) mod selection;
 pub(crate) mod storage;
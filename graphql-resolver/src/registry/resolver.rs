@@ -1,17 +1,73 @@
-use async_graphql::Value;
+use async_graphql::{Data, Value};
+use futures::Stream;
 use rustc_hash::FxHashMap;
+use std::any::{Any, TypeId};
 use std::future::Future;
 use std::pin::Pin;
+use std::sync::{Arc, Weak};
 
 use crate::error::ResolverError;
+use crate::registry::selection::SelectedField;
 
 pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+pub type BoxStream<'a, T> = Pin<Box<dyn Stream<Item = T> + Send + 'a>>;
 pub type ResolverResult<T> = Result<T, ResolverError>;
 
+#[derive(Clone, Default)]
+pub(crate) struct ContextData {
+    request: Arc<Data>,
+    shared: Arc<Data>,
+}
+
+impl ContextData {
+    pub fn new(request: Arc<Data>, shared: Arc<Data>) -> Self {
+        Self { request, shared }
+    }
+
+    pub fn get<T: Any + Send + Sync>(&self) -> Option<&T> {
+        let type_id = TypeId::of::<T>();
+        self.request
+            .get(&type_id)
+            .or_else(|| self.shared.get(&type_id))
+            .and_then(|data| data.downcast_ref::<T>())
+    }
+
+    pub fn with_request(mut self, request: Arc<Data>) -> Self {
+        self.request = request;
+        self
+    }
+
+    pub fn downgrade(&self) -> WeakContextData {
+        WeakContextData {
+            request: Arc::downgrade(&self.request),
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+// Batch loaders are stored in the request data, so they only hold a weak
+// reference to it.
+#[derive(Clone, Default)]
+pub(crate) struct WeakContextData {
+    request: Weak<Data>,
+    shared: Arc<Data>,
+}
+
+impl WeakContextData {
+    pub fn upgrade(&self) -> ContextData {
+        ContextData {
+            request: self.request.upgrade().unwrap_or_default(),
+            shared: self.shared.clone(),
+        }
+    }
+}
+
 pub struct ResolverContext {
     pub(crate) parent_value: Option<Value>,
     pub(crate) field_name: String,
     pub(crate) path: Vec<String>,
+    pub(crate) selection: Vec<SelectedField>,
+    pub(crate) data: ContextData,
 }
 
 impl ResolverContext {
@@ -20,6 +76,8 @@ impl ResolverContext {
             parent_value: None,
             field_name,
             path: Vec::new(),
+            selection: Vec::new(),
+            data: ContextData::default(),
         }
     }
 
@@ -33,6 +91,16 @@ impl ResolverContext {
         self
     }
 
+    pub fn with_selection(mut self, selection: Vec<SelectedField>) -> Self {
+        self.selection = selection;
+        self
+    }
+
+    pub(crate) fn with_data(mut self, data: ContextData) -> Self {
+        self.data = data;
+        self
+    }
+
     pub fn parent_value(&self) -> Option<&Value> {
         self.parent_value.as_ref()
     }
@@ -44,6 +112,18 @@ impl ResolverContext {
     pub fn path(&self) -> &[String] {
         &self.path
     }
+
+    pub fn selection(&self) -> &[SelectedField] {
+        &self.selection
+    }
+
+    pub fn selected(&self, name: &str) -> Option<&SelectedField> {
+        self.selection.iter().find(|f| f.name() == name)
+    }
+
+    pub fn data<T: Any + Send + Sync>(&self) -> Option<&T> {
+        self.data.get()
+    }
 }
 
 pub trait Resolver: Send + Sync + 'static {
@@ -56,6 +136,17 @@ pub trait Resolver: Send + Sync + 'static {
     fn name(&self) -> &'static str;
 }
 
+#[allow(clippy::type_complexity)]
+pub trait SubscriptionResolver: Send + Sync + 'static {
+    fn subscribe<'a>(
+        &'a self,
+        ctx: &'a ResolverContext,
+        args: FxHashMap<String, Value>,
+    ) -> BoxFuture<'a, ResolverResult<BoxStream<'static, ResolverResult<Value>>>>;
+
+    fn name(&self) -> &'static str;
+}
+
 #[allow(clippy::type_complexity)]
 pub trait BatchResolver: Send + Sync + 'static {
     type Key: Clone + Eq + std::hash::Hash + Send + Sync + 'static;
@@ -67,8 +158,26 @@ pub trait BatchResolver: Send + Sync + 'static {
         keys: Vec<Self::Key>,
     ) -> BoxFuture<'a, ResolverResult<Vec<(Self::Key, Self::Value)>>>;
 
+    fn load_each<'a>(
+        &'a self,
+        ctx: &'a ResolverContext,
+        keys: Vec<Self::Key>,
+    ) -> BoxFuture<'a, ResolverResult<Vec<(Self::Key, ResolverResult<Self::Value>)>>> {
+        Box::pin(async move {
+            let results = self.load(ctx, keys).await?;
+            Ok(results
+                .into_iter()
+                .map(|(key, value)| (key, Ok(value)))
+                .collect())
+        })
+    }
+
     fn name(&self) -> &'static str;
     fn batch_key_field(&self) -> &'static str;
+
+    fn cache_results(&self) -> bool {
+        true
+    }
 }
 
 #[cfg(test)]
@@ -113,4 +222,33 @@ mod tests {
         assert_eq!(ctx.parent_value().unwrap(), &Value::Number(42.into()));
         assert_eq!(ctx.path().len(), 2);
     }
+
+    #[test]
+    fn test_resolver_context_with_selection() {
+        let ctx = ResolverContext::new("user".to_string()).with_selection(vec![
+            SelectedField::new("id"),
+            SelectedField::new("posts").with_selection(vec![SelectedField::new("title")]),
+        ]);
+
+        assert_eq!(ctx.selection().len(), 2);
+        assert!(ctx.selected("id").is_some());
+        assert!(ctx.selected("name").is_none());
+        assert!(ctx.selected("posts").unwrap().field("title").is_some());
+    }
+
+    #[test]
+    fn test_resolver_context_data() {
+        let mut request = Data::default();
+        request.insert(1u32);
+        let mut shared = Data::default();
+        shared.insert(2u32);
+        shared.insert("shared");
+
+        let ctx = ResolverContext::new("field".to_string())
+            .with_data(ContextData::new(Arc::new(request), Arc::new(shared)));
+
+        assert_eq!(ctx.data::<u32>(), Some(&1));
+        assert_eq!(ctx.data::<&str>(), Some(&"shared"));
+        assert!(ctx.data::<String>().is_none());
+    }
 }
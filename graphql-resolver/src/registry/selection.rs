@@ -0,0 +1,187 @@
+use async_graphql::{SelectionField, Value};
+use rustc_hash::FxHashMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelectedField {
+    name: String,
+    alias: Option<String>,
+    arguments: FxHashMap<String, Value>,
+    selection: Vec<SelectedField>,
+}
+
+impl SelectedField {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            alias: None,
+            arguments: FxHashMap::default(),
+            selection: Vec::new(),
+        }
+    }
+
+    pub fn with_alias(mut self, alias: impl Into<String>) -> Self {
+        self.alias = Some(alias.into());
+        self
+    }
+
+    pub fn with_arguments(mut self, arguments: FxHashMap<String, Value>) -> Self {
+        self.arguments = arguments;
+        self
+    }
+
+    pub fn with_selection(mut self, selection: Vec<SelectedField>) -> Self {
+        self.selection = selection;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn alias(&self) -> Option<&str> {
+        self.alias.as_deref()
+    }
+
+    pub fn response_key(&self) -> &str {
+        self.alias.as_deref().unwrap_or(&self.name)
+    }
+
+    pub fn arguments(&self) -> &FxHashMap<String, Value> {
+        &self.arguments
+    }
+
+    pub fn selection(&self) -> &[SelectedField] {
+        &self.selection
+    }
+
+    pub fn field(&self, name: &str) -> Option<&SelectedField> {
+        self.selection.iter().find(|f| f.name == name)
+    }
+}
+
+pub(crate) fn collect_selection<'a>(
+    fields: impl Iterator<Item = SelectionField<'a>>,
+) -> Vec<SelectedField> {
+    let mut selection = Vec::new();
+    for field in fields {
+        let arguments = field
+            .arguments()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect();
+        let selected = SelectedField {
+            name: field.name().to_string(),
+            alias: field.alias().map(str::to_string),
+            arguments,
+            selection: collect_selection(field.selection_set()),
+        };
+        merge_selection(&mut selection, std::slice::from_ref(&selected));
+    }
+    selection
+}
+
+pub(crate) fn merge_selection(target: &mut Vec<SelectedField>, other: &[SelectedField]) {
+    for field in other {
+        match target
+            .iter_mut()
+            .find(|f| f.response_key() == field.response_key() && f.name == field.name)
+        {
+            Some(existing) => merge_selection(&mut existing.selection, &field.selection),
+            None => target.push(field.clone()),
+        }
+    }
+}
+
+// Whether `selection` already includes every field of `other`, so a value
+// fetched for `selection` can also answer `other`.
+pub(crate) fn selection_covers(selection: &[SelectedField], other: &[SelectedField]) -> bool {
+    other.iter().all(|field| {
+        selection.iter().any(|f| {
+            f.response_key() == field.response_key()
+                && f.name == field.name
+                && selection_covers(&f.selection, &field.selection)
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_selected_field_builder() {
+        let mut arguments = FxHashMap::default();
+        arguments.insert("first".to_string(), Value::Number(10.into()));
+        let field = SelectedField::new("posts")
+            .with_alias("recent")
+            .with_arguments(arguments)
+            .with_selection(vec![SelectedField::new("id"), SelectedField::new("title")]);
+
+        assert_eq!(field.name(), "posts");
+        assert_eq!(field.alias(), Some("recent"));
+        assert_eq!(field.response_key(), "recent");
+        assert_eq!(
+            field.arguments().get("first"),
+            Some(&Value::Number(10.into()))
+        );
+        assert!(field.field("title").is_some());
+        assert!(field.field("body").is_none());
+    }
+
+    #[test]
+    fn test_merge_selection() {
+        let mut target = vec![
+            SelectedField::new("id"),
+            SelectedField::new("posts").with_selection(vec![SelectedField::new("title")]),
+        ];
+        merge_selection(
+            &mut target,
+            &[
+                SelectedField::new("posts").with_selection(vec![SelectedField::new("body")]),
+                SelectedField::new("posts").with_alias("recent"),
+                SelectedField::new("id"),
+            ],
+        );
+
+        let keys: Vec<_> = target.iter().map(|f| f.response_key()).collect();
+        assert_eq!(keys, ["id", "posts", "recent"]);
+        assert_eq!(
+            target[1].selection(),
+            [SelectedField::new("title"), SelectedField::new("body")]
+        );
+    }
+
+    #[test]
+    fn test_selection_covers() {
+        let wide = vec![
+            SelectedField::new("id"),
+            SelectedField::new("posts").with_selection(vec![
+                SelectedField::new("title"),
+                SelectedField::new("body"),
+            ]),
+        ];
+
+        assert!(selection_covers(&wide, &[]));
+        assert!(selection_covers(
+            &wide,
+            &[SelectedField::new("posts").with_selection(vec![SelectedField::new("title")])]
+        ));
+        assert!(!selection_covers(&wide, &[SelectedField::new("name")]));
+        assert!(!selection_covers(
+            &wide,
+            &[SelectedField::new("posts").with_selection(vec![SelectedField::new("tags")])]
+        ));
+        assert!(!selection_covers(
+            &wide,
+            &[SelectedField::new("id").with_alias("key")]
+        ));
+    }
+
+    #[test]
+    fn test_response_key_defaults_to_name() {
+        let field = SelectedField::new("id");
+        assert_eq!(field.response_key(), "id");
+        assert!(field.selection().is_empty());
+    }
+}
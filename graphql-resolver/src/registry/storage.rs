@@ -1,10 +1,16 @@
 use std::sync::Arc;
 
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 
 use crate::error::ResolverError;
-use crate::registry::resolver::{BoxFuture, Resolver, ResolverContext, ResolverResult};
+use crate::registry::resolver::{
+    BatchResolver, BoxFuture, Resolver, ResolverContext, ResolverResult, SubscriptionResolver,
+};
+use crate::scalar::ScalarCodec;
 
+#[allow(clippy::type_complexity)]
 pub trait ErasedBatchResolver: Send + Sync {
     fn name(&self) -> &'static str;
     fn batch_key_field(&self) -> &'static str;
@@ -13,12 +19,114 @@ pub trait ErasedBatchResolver: Send + Sync {
         ctx: &'a ResolverContext,
         keys: Vec<serde_json::Value>,
     ) -> BoxFuture<'a, ResolverResult<Vec<(serde_json::Value, serde_json::Value)>>>;
+
+    fn load_erased_each<'a>(
+        &'a self,
+        ctx: &'a ResolverContext,
+        keys: Vec<serde_json::Value>,
+    ) -> BoxFuture<'a, ResolverResult<Vec<(serde_json::Value, ResolverResult<serde_json::Value>)>>>
+    {
+        Box::pin(async move {
+            let results = self.load_erased(ctx, keys).await?;
+            Ok(results
+                .into_iter()
+                .map(|(key, value)| (key, Ok(value)))
+                .collect())
+        })
+    }
+
+    fn cache_results(&self) -> bool {
+        true
+    }
+}
+
+impl<T> ErasedBatchResolver for T
+where
+    T: BatchResolver,
+    T::Key: Serialize + DeserializeOwned,
+    T::Value: Serialize,
+{
+    fn name(&self) -> &'static str {
+        BatchResolver::name(self)
+    }
+
+    fn batch_key_field(&self) -> &'static str {
+        BatchResolver::batch_key_field(self)
+    }
+
+    fn load_erased<'a>(
+        &'a self,
+        ctx: &'a ResolverContext,
+        keys: Vec<serde_json::Value>,
+    ) -> BoxFuture<'a, ResolverResult<Vec<(serde_json::Value, serde_json::Value)>>> {
+        Box::pin(async move {
+            let typed_keys = keys
+                .into_iter()
+                .map(|key| {
+                    serde_json::from_value::<T::Key>(key.clone())
+                        .map_err(|e| invalid_batch_key(self, &key, e))
+                })
+                .collect::<ResolverResult<Vec<_>>>()?;
+
+            let results = self.load(ctx, typed_keys).await?;
+
+            results
+                .into_iter()
+                .map(|(key, value)| Ok((serde_json::to_value(key)?, serde_json::to_value(value)?)))
+                .collect()
+        })
+    }
+
+    fn load_erased_each<'a>(
+        &'a self,
+        ctx: &'a ResolverContext,
+        keys: Vec<serde_json::Value>,
+    ) -> BoxFuture<'a, ResolverResult<Vec<(serde_json::Value, ResolverResult<serde_json::Value>)>>>
+    {
+        Box::pin(async move {
+            let mut results = Vec::with_capacity(keys.len());
+            let mut typed_keys = Vec::with_capacity(keys.len());
+            for key in keys {
+                match serde_json::from_value::<T::Key>(key.clone()) {
+                    Ok(typed_key) => typed_keys.push(typed_key),
+                    Err(e) => {
+                        let err = invalid_batch_key(self, &key, e);
+                        results.push((key, Err(err)));
+                    }
+                }
+            }
+
+            if !typed_keys.is_empty() {
+                for (key, value) in self.load_each(ctx, typed_keys).await? {
+                    let value = value.and_then(|v| Ok(serde_json::to_value(v)?));
+                    results.push((serde_json::to_value(key)?, value));
+                }
+            }
+            Ok(results)
+        })
+    }
+
+    fn cache_results(&self) -> bool {
+        BatchResolver::cache_results(self)
+    }
+}
+
+fn invalid_batch_key<T: BatchResolver>(
+    resolver: &T,
+    key: &serde_json::Value,
+    err: serde_json::Error,
+) -> ResolverError {
+    ResolverError::Execution(format!(
+        "Invalid batch key {} for batch resolver '{}' (@batchKey field '{}'): {}",
+        key,
+        resolver.name(),
+        resolver.batch_key_field(),
+        err
+    ))
 }
 
 pub struct ResolverRegistration {
-    #[allow(dead_code)]
     pub(crate) factory: fn() -> Box<dyn Resolver>,
-    #[allow(dead_code)]
     pub(crate) name: &'static str,
 }
 
@@ -31,9 +139,7 @@ impl ResolverRegistration {
 inventory::collect!(ResolverRegistration);
 
 pub struct BatchResolverRegistration {
-    #[allow(dead_code)]
     pub(crate) factory: fn() -> Box<dyn ErasedBatchResolver>,
-    #[allow(dead_code)]
     pub(crate) name: &'static str,
     #[allow(dead_code)]
     pub(crate) batch_key: &'static str,
@@ -58,6 +164,9 @@ inventory::collect!(BatchResolverRegistration);
 pub struct TraitRegistry {
     resolvers: FxHashMap<String, Arc<dyn Resolver>>,
     batch_resolvers: FxHashMap<String, Arc<dyn ErasedBatchResolver>>,
+    subscription_resolvers: FxHashMap<String, Arc<dyn SubscriptionResolver>>,
+    scalar_codecs: FxHashMap<String, Arc<dyn ScalarCodec>>,
+    duplicates: Vec<(&'static str, String)>,
 }
 
 impl TraitRegistry {
@@ -65,40 +174,123 @@ impl TraitRegistry {
         Self {
             resolvers: FxHashMap::default(),
             batch_resolvers: FxHashMap::default(),
+            subscription_resolvers: FxHashMap::default(),
+            scalar_codecs: FxHashMap::default(),
+            duplicates: Vec::new(),
         }
     }
 
-    #[allow(dead_code)]
-    pub(crate) fn from_inventory() -> Self {
-        let mut registry = Self::new();
+    // Registrations submitted through `inventory` never replace explicit ones;
+    // every name collision is returned as a conflict instead.
+    pub(crate) fn merge_inventory(&mut self) -> Vec<String> {
+        let mut conflicts = Vec::new();
+        let mut submitted = FxHashSet::default();
 
         for registration in inventory::iter::<ResolverRegistration> {
-            let resolver = (registration.factory)();
-            registry
-                .resolvers
-                .insert(registration.name.to_string(), Arc::from(resolver));
+            if !submitted.insert(registration.name) {
+                conflicts.push(format!(
+                    "resolver '{}' is submitted to inventory more than once",
+                    registration.name
+                ));
+            } else if self.is_registered(registration.name) {
+                conflicts.push(format!(
+                    "resolver '{}' is registered both explicitly and through inventory",
+                    registration.name
+                ));
+            } else {
+                let resolver = (registration.factory)();
+                self.resolvers
+                    .insert(registration.name.to_string(), Arc::from(resolver));
+            }
         }
 
+        submitted.clear();
         for registration in inventory::iter::<BatchResolverRegistration> {
-            let resolver = (registration.factory)();
-            registry
-                .batch_resolvers
-                .insert(registration.name.to_string(), Arc::from(resolver));
+            if !submitted.insert(registration.name) {
+                conflicts.push(format!(
+                    "batch resolver '{}' is submitted to inventory more than once",
+                    registration.name
+                ));
+            } else if self.is_registered(registration.name) {
+                conflicts.push(format!(
+                    "batch resolver '{}' is registered both explicitly and through inventory",
+                    registration.name
+                ));
+            } else {
+                let resolver = (registration.factory)();
+                self.batch_resolvers
+                    .insert(registration.name.to_string(), Arc::from(resolver));
+            }
         }
 
-        registry
+        conflicts
     }
 
     pub fn register_resolver<R: Resolver>(&mut self, resolver: R) {
         let name = resolver.name().to_string();
+        if self.is_registered(&name) {
+            self.duplicates.push(("resolver", name.clone()));
+        }
         self.resolvers.insert(name, Arc::new(resolver));
     }
 
     pub fn register_batch_resolver<R: ErasedBatchResolver + 'static>(&mut self, resolver: R) {
         let name = resolver.name().to_string();
+        if self.is_registered(&name) {
+            self.duplicates.push(("resolver", name.clone()));
+        }
+        self.batch_resolvers.insert(name, Arc::new(resolver));
+    }
+
+    pub fn replace_resolver<R: Resolver>(&mut self, resolver: R) {
+        let name = resolver.name().to_string();
+        self.clear_duplicates("resolver", &name);
+        self.batch_resolvers.remove(&name);
+        self.resolvers.insert(name, Arc::new(resolver));
+    }
+
+    pub fn replace_batch_resolver<R: ErasedBatchResolver + 'static>(&mut self, resolver: R) {
+        let name = resolver.name().to_string();
+        self.clear_duplicates("resolver", &name);
+        self.resolvers.remove(&name);
         self.batch_resolvers.insert(name, Arc::new(resolver));
     }
 
+    fn is_registered(&self, name: &str) -> bool {
+        self.resolvers.contains_key(name) || self.batch_resolvers.contains_key(name)
+    }
+
+    fn clear_duplicates(&mut self, kind: &str, name: &str) {
+        self.duplicates.retain(|(k, n)| *k != kind || n != name);
+    }
+
+    pub(crate) fn duplicates(&self) -> Vec<String> {
+        self.duplicates
+            .iter()
+            .map(|(kind, name)| format!("{} '{}' is registered more than once", kind, name))
+            .collect()
+    }
+
+    pub fn register_subscription_resolver<R: SubscriptionResolver>(&mut self, resolver: R) {
+        let name = resolver.name().to_string();
+        if self.subscription_resolvers.contains_key(&name) {
+            self.duplicates
+                .push(("subscription resolver", name.clone()));
+        }
+        self.subscription_resolvers.insert(name, Arc::new(resolver));
+    }
+
+    pub fn replace_subscription_resolver<R: SubscriptionResolver>(&mut self, resolver: R) {
+        let name = resolver.name().to_string();
+        self.clear_duplicates("subscription resolver", &name);
+        self.subscription_resolvers.insert(name, Arc::new(resolver));
+    }
+
+    pub fn register_scalar_codec<C: ScalarCodec>(&mut self, codec: C) {
+        let name = codec.name().to_string();
+        self.scalar_codecs.insert(name, Arc::new(codec));
+    }
+
     pub fn get_resolver(&self, name: &str) -> ResolverResult<Arc<dyn Resolver>> {
         self.resolvers
             .get(name)
@@ -113,7 +305,20 @@ impl TraitRegistry {
             .ok_or_else(|| ResolverError::NotFound(name.to_string()))
     }
 
-    #[allow(dead_code)]
+    pub fn get_subscription_resolver(
+        &self,
+        name: &str,
+    ) -> ResolverResult<Arc<dyn SubscriptionResolver>> {
+        self.subscription_resolvers
+            .get(name)
+            .cloned()
+            .ok_or_else(|| ResolverError::NotFound(name.to_string()))
+    }
+
+    pub fn get_scalar_codec(&self, name: &str) -> Option<Arc<dyn ScalarCodec>> {
+        self.scalar_codecs.get(name).cloned()
+    }
+
     pub(crate) fn has_resolver(&self, name: &str) -> bool {
         self.resolvers.contains_key(name)
     }
@@ -122,15 +327,21 @@ impl TraitRegistry {
         self.batch_resolvers.contains_key(name)
     }
 
-    #[allow(dead_code)]
+    pub(crate) fn has_subscription_resolver(&self, name: &str) -> bool {
+        self.subscription_resolvers.contains_key(name)
+    }
+
     pub(crate) fn resolver_names(&self) -> impl Iterator<Item = &String> {
         self.resolvers.keys()
     }
 
-    #[allow(dead_code)]
     pub(crate) fn batch_resolver_names(&self) -> impl Iterator<Item = &String> {
         self.batch_resolvers.keys()
     }
+
+    pub(crate) fn subscription_resolver_names(&self) -> impl Iterator<Item = &String> {
+        self.subscription_resolvers.keys()
+    }
 }
 
 impl Default for TraitRegistry {
@@ -180,6 +391,53 @@ mod tests {
         }
     }
 
+    struct TestSubscriptionResolver;
+
+    impl SubscriptionResolver for TestSubscriptionResolver {
+        fn subscribe<'a>(
+            &'a self,
+            _ctx: &'a ResolverContext,
+            _args: FxHashMap<String, Value>,
+        ) -> BoxFuture<
+            'a,
+            ResolverResult<crate::registry::resolver::BoxStream<'static, ResolverResult<Value>>>,
+        > {
+            Box::pin(async { Ok(Box::pin(futures::stream::empty()) as _) })
+        }
+
+        fn name(&self) -> &'static str {
+            "testSubscription"
+        }
+    }
+
+    struct TypedBatchResolver;
+
+    impl BatchResolver for TypedBatchResolver {
+        type Key = i64;
+        type Value = String;
+
+        fn load<'a>(
+            &'a self,
+            _ctx: &'a ResolverContext,
+            keys: Vec<Self::Key>,
+        ) -> BoxFuture<'a, ResolverResult<Vec<(Self::Key, Self::Value)>>> {
+            Box::pin(async move {
+                Ok(keys
+                    .into_iter()
+                    .map(|k| (k, format!("value-{}", k)))
+                    .collect())
+            })
+        }
+
+        fn name(&self) -> &'static str {
+            "typedBatchResolver"
+        }
+
+        fn batch_key_field(&self) -> &'static str {
+            "authorId"
+        }
+    }
+
     #[test]
     fn test_registry_new() {
         let registry = TraitRegistry::new();
@@ -232,6 +490,55 @@ mod tests {
         assert_eq!(resolver.batch_key_field(), "id");
     }
 
+    #[test]
+    fn test_batch_resolver_caches_results_by_default() {
+        assert!(TestBatchResolver.cache_results());
+    }
+
+    #[test]
+    fn test_register_typed_batch_resolver() {
+        let mut registry = TraitRegistry::new();
+        registry.register_batch_resolver(TypedBatchResolver);
+
+        let resolver = registry.get_batch_resolver("typedBatchResolver").unwrap();
+        assert_eq!(resolver.batch_key_field(), "authorId");
+        assert!(resolver.cache_results());
+    }
+
+    #[test]
+    fn test_typed_batch_resolver_load_erased() {
+        let ctx = ResolverContext::new("author".to_string());
+        let keys = vec![serde_json::json!(1), serde_json::json!(2)];
+
+        let results =
+            futures::executor::block_on(TypedBatchResolver.load_erased(&ctx, keys)).unwrap();
+
+        assert_eq!(
+            results,
+            vec![
+                (serde_json::json!(1), serde_json::json!("value-1")),
+                (serde_json::json!(2), serde_json::json!("value-2")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_typed_batch_resolver_invalid_key() {
+        let ctx = ResolverContext::new("author".to_string());
+        let keys = vec![serde_json::json!("not-a-number")];
+
+        let result = futures::executor::block_on(TypedBatchResolver.load_erased(&ctx, keys));
+
+        match result {
+            Err(ResolverError::Execution(msg)) => {
+                assert!(msg.contains("\"not-a-number\""));
+                assert!(msg.contains("typedBatchResolver"));
+                assert!(msg.contains("authorId"));
+            }
+            _ => panic!("Expected Execution error"),
+        }
+    }
+
     #[test]
     fn test_get_batch_resolver_not_found() {
         let registry = TraitRegistry::new();
@@ -239,6 +546,30 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_register_and_get_subscription_resolver() {
+        let mut registry = TraitRegistry::new();
+        registry.register_subscription_resolver(TestSubscriptionResolver);
+
+        assert!(registry.has_subscription_resolver("testSubscription"));
+        assert!(!registry.has_resolver("testSubscription"));
+
+        let resolver = registry
+            .get_subscription_resolver("testSubscription")
+            .unwrap();
+        assert_eq!(resolver.name(), "testSubscription");
+        assert_eq!(registry.subscription_resolver_names().count(), 1);
+    }
+
+    #[test]
+    fn test_get_subscription_resolver_not_found() {
+        let registry = TraitRegistry::new();
+        match registry.get_subscription_resolver("nonexistent") {
+            Err(ResolverError::NotFound(name)) => assert_eq!(name, "nonexistent"),
+            _ => panic!("Expected NotFound error"),
+        }
+    }
+
     #[test]
     fn test_resolver_names() {
         let mut registry = TraitRegistry::new();
@@ -281,8 +612,109 @@ mod tests {
     }
 
     #[test]
-    fn test_from_inventory() {
-        let registry = TraitRegistry::from_inventory();
-        let _ = registry.resolver_names().count();
+    fn test_duplicate_registrations_are_recorded() {
+        let mut registry = TraitRegistry::new();
+        registry.register_resolver(TestResolver);
+        assert!(registry.duplicates().is_empty());
+
+        registry.register_resolver(TestResolver);
+        assert_eq!(
+            registry.duplicates(),
+            ["resolver 'testResolver' is registered more than once"]
+        );
+    }
+
+    #[test]
+    fn test_duplicate_across_resolver_kinds() {
+        struct ClashingBatchResolver;
+
+        impl ErasedBatchResolver for ClashingBatchResolver {
+            fn name(&self) -> &'static str {
+                "testResolver"
+            }
+
+            fn batch_key_field(&self) -> &'static str {
+                "id"
+            }
+
+            fn load_erased<'a>(
+                &'a self,
+                _ctx: &'a ResolverContext,
+                _keys: Vec<serde_json::Value>,
+            ) -> BoxFuture<'a, ResolverResult<Vec<(serde_json::Value, serde_json::Value)>>>
+            {
+                Box::pin(async { Ok(Vec::new()) })
+            }
+        }
+
+        let mut registry = TraitRegistry::new();
+        registry.register_resolver(TestResolver);
+        registry.register_batch_resolver(ClashingBatchResolver);
+        assert_eq!(registry.duplicates().len(), 1);
+
+        let mut registry = TraitRegistry::new();
+        registry.register_resolver(TestResolver);
+        registry.replace_batch_resolver(ClashingBatchResolver);
+        assert!(registry.duplicates().is_empty());
+        assert!(!registry.has_resolver("testResolver"));
+        assert!(registry.has_batch_resolver("testResolver"));
+
+        registry.replace_resolver(TestResolver);
+        assert!(registry.duplicates().is_empty());
+        assert!(registry.has_resolver("testResolver"));
+        assert!(!registry.has_batch_resolver("testResolver"));
+    }
+
+    #[test]
+    fn test_replace_clears_recorded_duplicate() {
+        let mut registry = TraitRegistry::new();
+        registry.register_resolver(TestResolver);
+        registry.register_resolver(TestResolver);
+        assert_eq!(registry.duplicates().len(), 1);
+
+        registry.replace_resolver(TestResolver);
+        assert!(registry.duplicates().is_empty());
+        assert!(registry.has_resolver("testResolver"));
+    }
+
+    #[test]
+    fn test_duplicate_subscription_resolvers() {
+        let mut registry = TraitRegistry::new();
+        registry.register_subscription_resolver(TestSubscriptionResolver);
+        registry.register_resolver(TestResolver);
+        assert!(registry.duplicates().is_empty());
+
+        registry.register_subscription_resolver(TestSubscriptionResolver);
+        assert_eq!(
+            registry.duplicates(),
+            ["subscription resolver 'testSubscription' is registered more than once"]
+        );
+
+        registry.replace_subscription_resolver(TestSubscriptionResolver);
+        assert!(registry.duplicates().is_empty());
+        assert!(registry.has_subscription_resolver("testSubscription"));
+    }
+
+    inventory::submit! {
+        ResolverRegistration::new(|| Box::new(TestResolver), "testResolver")
+    }
+
+    #[test]
+    fn test_merge_inventory() {
+        let mut registry = TraitRegistry::new();
+        assert!(registry.merge_inventory().is_empty());
+        assert!(registry.has_resolver("testResolver"));
+    }
+
+    #[test]
+    fn test_merge_inventory_conflicts_with_explicit_registration() {
+        let mut registry = TraitRegistry::new();
+        registry.register_resolver(TestResolver);
+
+        let conflicts = registry.merge_inventory();
+        assert_eq!(
+            conflicts,
+            vec!["resolver 'testResolver' is registered both explicitly and through inventory"]
+        );
     }
 }
@@ -0,0 +1,75 @@
+use async_graphql::Value;
+
+use super::{invalid_scalar, ScalarCodec};
+use crate::registry::resolver::ResolverResult;
+
+pub struct BigIntCodec;
+
+impl BigIntCodec {
+    fn normalize(&self, value: Value) -> ResolverResult<Value> {
+        match &value {
+            Value::Number(n) if n.is_i64() || n.is_u64() => Ok(Value::String(n.to_string())),
+            Value::String(s) => {
+                let digits = s.strip_prefix('-').unwrap_or(s);
+                if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(invalid_scalar(self.name(), &value, "expected an integer"));
+                }
+
+                let trimmed = digits.trim_start_matches('0');
+                let normalized = match (trimmed.is_empty(), s.starts_with('-')) {
+                    (true, _) => "0".to_string(),
+                    (false, true) => format!("-{}", trimmed),
+                    (false, false) => trimmed.to_string(),
+                };
+                Ok(Value::String(normalized))
+            }
+            _ => Err(invalid_scalar(self.name(), &value, "expected an integer")),
+        }
+    }
+}
+
+impl ScalarCodec for BigIntCodec {
+    fn name(&self) -> &'static str {
+        "BigInt"
+    }
+
+    fn parse(&self, value: Value) -> ResolverResult<Value> {
+        self.normalize(value)
+    }
+
+    fn serialize(&self, value: Value) -> ResolverResult<Value> {
+        self.normalize(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bigint_accepts_numbers_and_strings() {
+        assert_eq!(
+            BigIntCodec.parse(Value::Number(42.into())).unwrap(),
+            Value::String("42".to_string())
+        );
+        assert_eq!(
+            BigIntCodec
+                .parse(Value::String(
+                    "-000123456789012345678901234567890".to_string()
+                ))
+                .unwrap(),
+            Value::String("-123456789012345678901234567890".to_string())
+        );
+        assert_eq!(
+            BigIntCodec.parse(Value::String("-0".to_string())).unwrap(),
+            Value::String("0".to_string())
+        );
+    }
+
+    #[test]
+    fn test_bigint_rejects_invalid_values() {
+        assert!(!BigIntCodec.validate(&Value::String("12a".to_string())));
+        assert!(!BigIntCodec.validate(&Value::String("-".to_string())));
+        assert!(!BigIntCodec.validate(&Value::from_json(serde_json::json!(1.5)).unwrap()));
+    }
+}
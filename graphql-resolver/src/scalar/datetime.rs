@@ -0,0 +1,60 @@
+use async_graphql::Value;
+use chrono::{DateTime, SecondsFormat, Utc};
+
+use super::{invalid_scalar, ScalarCodec};
+use crate::registry::resolver::ResolverResult;
+
+pub struct DateTimeCodec;
+
+impl DateTimeCodec {
+    fn normalize(&self, value: Value) -> ResolverResult<Value> {
+        match &value {
+            Value::String(s) => DateTime::parse_from_rfc3339(s)
+                .map(|dt| {
+                    Value::String(
+                        dt.with_timezone(&Utc)
+                            .to_rfc3339_opts(SecondsFormat::AutoSi, true),
+                    )
+                })
+                .map_err(|e| invalid_scalar(self.name(), &value, e)),
+            _ => Err(invalid_scalar(
+                self.name(),
+                &value,
+                "expected an RFC 3339 string",
+            )),
+        }
+    }
+}
+
+impl ScalarCodec for DateTimeCodec {
+    fn name(&self) -> &'static str {
+        "DateTime"
+    }
+
+    fn parse(&self, value: Value) -> ResolverResult<Value> {
+        self.normalize(value)
+    }
+
+    fn serialize(&self, value: Value) -> ResolverResult<Value> {
+        self.normalize(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_datetime_normalizes_to_utc() {
+        let value = DateTimeCodec
+            .parse(Value::String("2024-01-02T03:04:05+02:00".to_string()))
+            .unwrap();
+        assert_eq!(value, Value::String("2024-01-02T01:04:05Z".to_string()));
+    }
+
+    #[test]
+    fn test_datetime_rejects_invalid_values() {
+        assert!(!DateTimeCodec.validate(&Value::String("yesterday".to_string())));
+        assert!(!DateTimeCodec.validate(&Value::Number(1.into())));
+    }
+}
@@ -0,0 +1,28 @@
+use async_graphql::Value;
+
+use super::ScalarCodec;
+use crate::registry::resolver::ResolverResult;
+
+pub struct JsonCodec;
+
+impl ScalarCodec for JsonCodec {
+    fn name(&self) -> &'static str {
+        "JSON"
+    }
+
+    fn parse(&self, value: Value) -> ResolverResult<Value> {
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_accepts_any_value() {
+        let value = Value::from_json(serde_json::json!({"a": [1, "b", null]})).unwrap();
+        assert_eq!(JsonCodec.parse(value.clone()).unwrap(), value);
+        assert_eq!(JsonCodec.serialize(value.clone()).unwrap(), value);
+    }
+}
@@ -0,0 +1,88 @@
+#[cfg(feature = "bigint")]
+mod bigint;
+#[cfg(feature = "datetime")]
+mod datetime;
+#[cfg(feature = "json")]
+mod json;
+#[cfg(feature = "uuid")]
+mod uuid;
+
+#[cfg(feature = "bigint")]
+pub use bigint::BigIntCodec;
+#[cfg(feature = "datetime")]
+pub use datetime::DateTimeCodec;
+#[cfg(feature = "json")]
+pub use json::JsonCodec;
+#[cfg(feature = "uuid")]
+pub use uuid::UuidCodec;
+
+use async_graphql::Value;
+
+use crate::registry::resolver::ResolverResult;
+
+pub trait ScalarCodec: Send + Sync + 'static {
+    fn name(&self) -> &'static str;
+
+    fn parse(&self, value: Value) -> ResolverResult<Value>;
+
+    fn serialize(&self, value: Value) -> ResolverResult<Value> {
+        Ok(value)
+    }
+
+    fn validate(&self, value: &Value) -> bool {
+        self.parse(value.clone()).is_ok()
+    }
+}
+
+#[cfg(any(test, feature = "bigint", feature = "datetime", feature = "uuid"))]
+pub(crate) fn invalid_scalar(
+    name: &str,
+    value: &Value,
+    reason: impl std::fmt::Display,
+) -> crate::error::ResolverError {
+    crate::error::ResolverError::Argument(format!(
+        "Invalid value {} for scalar '{}': {}",
+        value, name, reason
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct UpperCodec;
+
+    impl ScalarCodec for UpperCodec {
+        fn name(&self) -> &'static str {
+            "Upper"
+        }
+
+        fn parse(&self, value: Value) -> ResolverResult<Value> {
+            match value {
+                Value::String(s) => Ok(Value::String(s.to_uppercase())),
+                other => Err(invalid_scalar(self.name(), &other, "expected a string")),
+            }
+        }
+    }
+
+    #[test]
+    fn test_default_validate_uses_parse() {
+        assert!(UpperCodec.validate(&Value::String("a".to_string())));
+        assert!(!UpperCodec.validate(&Value::Boolean(true)));
+    }
+
+    #[test]
+    fn test_default_serialize_is_identity() {
+        let value = Value::String("a".to_string());
+        assert_eq!(UpperCodec.serialize(value.clone()).unwrap(), value);
+    }
+
+    #[test]
+    fn test_invalid_scalar_error() {
+        let err = UpperCodec.parse(Value::Boolean(true)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Argument error: Invalid value true for scalar 'Upper': expected a string"
+        );
+    }
+}
@@ -0,0 +1,55 @@
+use async_graphql::Value;
+
+use super::{invalid_scalar, ScalarCodec};
+use crate::registry::resolver::ResolverResult;
+
+pub struct UuidCodec;
+
+impl UuidCodec {
+    fn normalize(&self, value: Value) -> ResolverResult<Value> {
+        match &value {
+            Value::String(s) => ::uuid::Uuid::parse_str(s)
+                .map(|uuid| Value::String(uuid.hyphenated().to_string()))
+                .map_err(|e| invalid_scalar(self.name(), &value, e)),
+            _ => Err(invalid_scalar(self.name(), &value, "expected a string")),
+        }
+    }
+}
+
+impl ScalarCodec for UuidCodec {
+    fn name(&self) -> &'static str {
+        "UUID"
+    }
+
+    fn parse(&self, value: Value) -> ResolverResult<Value> {
+        self.normalize(value)
+    }
+
+    fn serialize(&self, value: Value) -> ResolverResult<Value> {
+        self.normalize(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uuid_normalizes_to_hyphenated_lowercase() {
+        let value = UuidCodec
+            .parse(Value::String(
+                "67E5504410B1426F9247BB680E5FE0C8".to_string(),
+            ))
+            .unwrap();
+        assert_eq!(
+            value,
+            Value::String("67e55044-10b1-426f-9247-bb680e5fe0c8".to_string())
+        );
+    }
+
+    #[test]
+    fn test_uuid_rejects_invalid_values() {
+        assert!(!UuidCodec.validate(&Value::String("not-a-uuid".to_string())));
+        assert!(!UuidCodec.validate(&Value::Number(1.into())));
+    }
+}
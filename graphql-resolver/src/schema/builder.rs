@@ -1,21 +1,64 @@
 use std::sync::Arc;
-
-use async_graphql::dynamic::{Field, FieldFuture, Object, Schema, TypeRef};
-use async_graphql::Value;
-
-use crate::config::{FieldConfig, FieldType, GraphQLConfig, TypeConfig};
-use crate::error::ResolverError;
+use std::time::Duration;
+
+use async_graphql::dynamic::{
+    Enum, EnumItem, Field, FieldFuture, InputObject, InputValue, Interface, InterfaceField, Object,
+    Scalar, Schema, Subscription, TypeRef, Union,
+};
+use async_graphql::{Data, Name, Value};
+
+use crate::config::{
+    ArgumentConfig, EnumConfig, FieldConfig, FieldType, GraphQLConfig, InputObjectConfig,
+    ObjectKind, ScalarConfig, TypeConfig, UnionConfig,
+};
+use crate::error::{ErrorMapper, ResolverError};
 use crate::registry::storage::TraitRegistry;
-use crate::schema::field_resolver::{value_to_field_value, FieldResolverFactory};
+use crate::schema::extension::RequestExtension;
+use crate::schema::field_resolver::FieldResolverFactory;
+use crate::schema::input::InputCoercion;
+use crate::schema::output::OutputCoercion;
 
 pub(crate) struct SchemaBuilder {
     config: GraphQLConfig,
     registry: Arc<TraitRegistry>,
+    input_coercion: Arc<InputCoercion>,
+    output_coercion: Arc<OutputCoercion>,
+    shared_data: Arc<Data>,
+    errors: ErrorMapper,
+    batch_delay: Duration,
+    max_batch_size: usize,
 }
 
 impl SchemaBuilder {
     pub fn new(config: GraphQLConfig, registry: Arc<TraitRegistry>) -> Self {
-        Self { config, registry }
+        let input_coercion = Arc::new(InputCoercion::from_config(&config, &registry));
+        let output_coercion = Arc::new(OutputCoercion::from_config(&config, &registry));
+        Self {
+            config,
+            registry,
+            input_coercion,
+            output_coercion,
+            shared_data: Arc::default(),
+            errors: ErrorMapper::default(),
+            batch_delay: Duration::from_millis(1),
+            max_batch_size: 100,
+        }
+    }
+
+    pub fn with_batching(mut self, delay: Duration, max_batch_size: usize) -> Self {
+        self.batch_delay = delay;
+        self.max_batch_size = max_batch_size;
+        self
+    }
+
+    pub fn with_shared_data(mut self, shared_data: Arc<Data>) -> Self {
+        self.shared_data = shared_data;
+        self
+    }
+
+    pub fn with_error_mapper(mut self, errors: ErrorMapper) -> Self {
+        self.errors = errors;
+        self
     }
 
     pub fn build(self) -> Result<Schema, ResolverError> {
@@ -25,13 +68,54 @@ impl SchemaBuilder {
             .clone()
             .unwrap_or_else(|| "Query".to_string());
 
-        let mut schema_builder = Schema::build(&query_type_name, None, None);
+        let mutation_type_name = self.config.mutation_type.clone();
+        let subscription_type_name = self.config.subscription_type.clone();
+
+        let mut schema_builder = Schema::build(
+            &query_type_name,
+            mutation_type_name.as_deref(),
+            subscription_type_name.as_deref(),
+        )
+        .extension(RequestExtension::new(
+            self.registry.clone(),
+            self.shared_data.clone(),
+            self.batch_delay,
+            self.max_batch_size,
+        ));
 
         for (type_name, type_config) in &self.config.types {
+            if subscription_type_name.as_deref() == Some(type_name.as_str()) {
+                let subscription = self.build_subscription_type(type_name, type_config)?;
+                schema_builder = schema_builder.register(subscription);
+                continue;
+            }
+
+            if let ObjectKind::Interface { .. } = type_config.kind {
+                let interface = self.build_interface_type(type_name, type_config);
+                schema_builder = schema_builder.register(interface);
+                continue;
+            }
+
             let object = self.build_object_type(type_name, type_config)?;
             schema_builder = schema_builder.register(object);
         }
 
+        for union_config in self.config.unions.values() {
+            schema_builder = schema_builder.register(build_union(union_config));
+        }
+
+        for input_config in self.config.input_types.values() {
+            schema_builder = schema_builder.register(self.build_input_object(input_config));
+        }
+
+        for enum_config in self.config.enums.values() {
+            schema_builder = schema_builder.register(build_enum(enum_config));
+        }
+
+        for scalar_config in self.config.scalars.values() {
+            schema_builder = schema_builder.register(self.build_scalar(scalar_config));
+        }
+
         schema_builder
             .finish()
             .map_err(|e| ResolverError::Execution(e.to_string()))
@@ -44,50 +128,269 @@ impl SchemaBuilder {
     ) -> Result<Object, ResolverError> {
         let mut object = Object::new(type_name);
 
+        if let Some(description) = &type_config.description {
+            object = object.description(description);
+        }
+
         for field_config in &type_config.fields {
             let field = self.build_field(type_name, field_config)?;
             object = object.field(field);
         }
 
+        for interface in &type_config.implements {
+            object = object.implement(interface);
+        }
+
         Ok(object)
     }
 
+    fn build_subscription_type(
+        &self,
+        type_name: &str,
+        type_config: &TypeConfig,
+    ) -> Result<Subscription, ResolverError> {
+        let mut subscription = Subscription::new(type_name);
+
+        if let Some(description) = &type_config.description {
+            subscription = subscription.description(description);
+        }
+
+        for field_config in &type_config.fields {
+            let factory = FieldResolverFactory::new(
+                type_name.to_string(),
+                field_config.clone(),
+                self.registry.clone(),
+            )
+            .with_input_coercion(self.input_coercion.clone())
+            .with_output_coercion(self.output_coercion.clone())
+            .with_error_mapper(self.errors.clone());
+            let type_ref = convert_field_type(&field_config.field_type);
+            let mut field = factory.create_subscription_field(type_ref)?;
+            if let Some(description) = &field_config.description {
+                field = field.description(description);
+            }
+            if let Some(reason) = &field_config.deprecation {
+                field = field.deprecation(Some(reason));
+            }
+            for arg in &field_config.arguments {
+                field = field.argument(self.build_input_value(arg));
+            }
+            subscription = subscription.field(field);
+        }
+
+        Ok(subscription)
+    }
+
+    fn build_input_object(&self, input_config: &InputObjectConfig) -> InputObject {
+        let mut input_object = InputObject::new(&input_config.name);
+
+        if let Some(description) = &input_config.description {
+            input_object = input_object.description(description);
+        }
+
+        for field_config in &input_config.fields {
+            input_object = input_object.field(self.build_input_value(field_config));
+        }
+
+        input_object
+    }
+
+    fn build_input_value(&self, arg: &ArgumentConfig) -> InputValue {
+        let mut input_value = InputValue::new(&arg.name, convert_field_type(&arg.arg_type));
+
+        if let Some(description) = &arg.description {
+            input_value = input_value.description(description);
+        }
+        if let Some(reason) = &arg.deprecation {
+            input_value = input_value.deprecation(Some(reason));
+        }
+
+        if let Some(default_value) = &arg.default_value {
+            input_value = input_value.default_value(input_default_value(
+                default_value,
+                &arg.arg_type,
+                &self.config,
+            ));
+        }
+
+        input_value
+    }
+
+    fn build_scalar(&self, scalar_config: &ScalarConfig) -> Scalar {
+        let mut scalar = Scalar::new(&scalar_config.name);
+
+        if let Some(description) = &scalar_config.description {
+            scalar = scalar.description(description);
+        }
+
+        match self.registry.get_scalar_codec(&scalar_config.name) {
+            Some(codec) => scalar.validator(move |value| codec.validate(value)),
+            None => scalar,
+        }
+    }
+
     fn build_field(
         &self,
         parent_type: &str,
         field_config: &FieldConfig,
     ) -> Result<Field, ResolverError> {
-        let field_name = field_config.name.clone();
         let type_ref = convert_field_type(&field_config.field_type);
 
-        if field_config.resolver.is_some() {
+        let mut field = if field_config.resolver.is_some() {
+            let is_mutation_root = self.config.mutation_type.as_deref() == Some(parent_type);
             let factory = FieldResolverFactory::new(
                 parent_type.to_string(),
                 field_config.clone(),
                 self.registry.clone(),
-            );
-            return factory.create_field(type_ref);
+            )
+            .with_input_coercion(self.input_coercion.clone())
+            .with_output_coercion(self.output_coercion.clone())
+            .with_error_mapper(self.errors.clone())
+            .with_mutation_root(is_mutation_root);
+            factory.create_field(type_ref)?
+        } else {
+            self.build_parent_field(field_config, type_ref)
+        };
+
+        if let Some(description) = &field_config.description {
+            field = field.description(description);
+        }
+        if let Some(reason) = &field_config.deprecation {
+            field = field.deprecation(Some(reason));
+        }
+
+        for arg in &field_config.arguments {
+            field = field.argument(self.build_input_value(arg));
         }
 
+        Ok(field)
+    }
+
+    fn build_parent_field(&self, field_config: &FieldConfig, type_ref: TypeRef) -> Field {
+        let field_name = field_config.name.clone();
         let field_name_clone = field_name.clone();
-        let mut field = Field::new(&field_name, type_ref, move |ctx| {
+        let field_type = field_config.field_type.clone();
+        let output_coercion = self.output_coercion.clone();
+        Field::new(&field_name, type_ref, move |ctx| {
             let field_name = field_name_clone.clone();
+            let field_type = field_type.clone();
+            let output_coercion = output_coercion.clone();
             FieldFuture::new(async move {
                 if let Ok(Value::Object(obj)) = ctx.parent_value.try_downcast_ref::<Value>() {
                     if let Some(value) = obj.get(field_name.as_str()) {
-                        return Ok(Some(value_to_field_value(value.clone())));
+                        return Ok(Some(
+                            output_coercion.field_value(value.clone(), &field_type)?,
+                        ));
                     }
                 }
                 Ok(None)
             })
-        });
+        })
+    }
 
-        for arg in &field_config.arguments {
-            let arg_type = convert_field_type(&arg.arg_type);
-            field = field.argument(async_graphql::dynamic::InputValue::new(&arg.name, arg_type));
+    fn build_interface_type(&self, type_name: &str, type_config: &TypeConfig) -> Interface {
+        let mut interface = Interface::new(type_name);
+
+        if let Some(description) = &type_config.description {
+            interface = interface.description(description);
         }
 
-        Ok(field)
+        for field_config in &type_config.fields {
+            let mut field = InterfaceField::new(
+                &field_config.name,
+                convert_field_type(&field_config.field_type),
+            );
+            if let Some(description) = &field_config.description {
+                field = field.description(description);
+            }
+            if let Some(reason) = &field_config.deprecation {
+                field = field.deprecation(Some(reason));
+            }
+            for arg in &field_config.arguments {
+                field = field.argument(self.build_input_value(arg));
+            }
+            interface = interface.field(field);
+        }
+
+        for parent in &type_config.implements {
+            interface = interface.implement(parent);
+        }
+
+        interface
+    }
+}
+
+fn build_union(union_config: &UnionConfig) -> Union {
+    let mut union = Union::new(&union_config.name);
+
+    if let Some(description) = &union_config.description {
+        union = union.description(description);
+    }
+
+    for member in &union_config.members {
+        union = union.possible_type(member);
+    }
+
+    union
+}
+
+fn build_enum(enum_config: &EnumConfig) -> Enum {
+    let mut enum_type = Enum::new(&enum_config.name);
+
+    if let Some(description) = &enum_config.description {
+        enum_type = enum_type.description(description);
+    }
+
+    for value in &enum_config.values {
+        let mut item = EnumItem::new(&value.name);
+        if let Some(description) = &value.description {
+            item = item.description(description);
+        }
+        if let Some(reason) = &value.deprecation {
+            item = item.deprecation(Some(reason));
+        }
+        enum_type = enum_type.item(item);
+    }
+
+    enum_type
+}
+
+pub(crate) fn input_default_value(
+    value: &serde_json::Value,
+    field_type: &FieldType,
+    config: &GraphQLConfig,
+) -> Value {
+    match (field_type, value) {
+        (FieldType::NonNull(inner), _) => input_default_value(value, inner, config),
+        (FieldType::List(inner), serde_json::Value::Array(items)) => Value::List(
+            items
+                .iter()
+                .map(|item| input_default_value(item, inner, config))
+                .collect(),
+        ),
+        (FieldType::List(inner), _) => input_default_value(value, inner, config),
+        (FieldType::Named(name), serde_json::Value::String(s))
+            if config.enums.contains_key(name) =>
+        {
+            Value::Enum(Name::new(s))
+        }
+        (FieldType::Named(name), serde_json::Value::Object(obj)) => {
+            match config.input_types.get(name) {
+                Some(input_config) => Value::Object(
+                    obj.iter()
+                        .map(|(key, item)| {
+                            let item = match input_config.fields.iter().find(|f| &f.name == key) {
+                                Some(field) => input_default_value(item, &field.arg_type, config),
+                                None => Value::from_json(item.clone()).unwrap_or(Value::Null),
+                            };
+                            (Name::new(key), item)
+                        })
+                        .collect(),
+                ),
+                None => Value::from_json(value.clone()).unwrap_or(Value::Null),
+            }
+        }
+        _ => Value::from_json(value.clone()).unwrap_or(Value::Null),
     }
 }
 
@@ -102,6 +405,18 @@ pub(crate) fn convert_field_type(field_type: &FieldType) -> TypeRef {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::EnumValueConfig;
+
+    fn enum_values(names: &[&str]) -> Vec<EnumValueConfig> {
+        names
+            .iter()
+            .map(|name| EnumValueConfig {
+                name: name.to_string(),
+                description: None,
+                deprecation: None,
+            })
+            .collect()
+    }
 
     #[test]
     fn test_convert_field_type_named() {
@@ -140,6 +455,82 @@ mod tests {
         assert!(debug.contains("User"));
     }
 
+    #[test]
+    fn test_input_default_value_enum() {
+        let mut config = GraphQLConfig::default();
+        config.enums.insert(
+            "Role".to_string(),
+            EnumConfig {
+                name: "Role".to_string(),
+                description: None,
+                values: enum_values(&["ADMIN", "MEMBER"]),
+            },
+        );
+
+        let ft = FieldType::NonNull(Box::new(FieldType::Named("Role".to_string())));
+        let value = input_default_value(&serde_json::json!("ADMIN"), &ft, &config);
+        assert_eq!(value, Value::Enum(Name::new("ADMIN")));
+
+        let ft = FieldType::List(Box::new(FieldType::Named("Role".to_string())));
+        let value = input_default_value(&serde_json::json!(["ADMIN", "MEMBER"]), &ft, &config);
+        assert_eq!(
+            value,
+            Value::List(vec![
+                Value::Enum(Name::new("ADMIN")),
+                Value::Enum(Name::new("MEMBER")),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_input_default_value_input_object() {
+        let mut config = GraphQLConfig::default();
+        config.enums.insert(
+            "Role".to_string(),
+            EnumConfig {
+                name: "Role".to_string(),
+                description: None,
+                values: enum_values(&["ADMIN"]),
+            },
+        );
+        config.input_types.insert(
+            "Filter".to_string(),
+            InputObjectConfig {
+                name: "Filter".to_string(),
+                description: None,
+                fields: vec![ArgumentConfig {
+                    name: "role".to_string(),
+                    arg_type: FieldType::Named("Role".to_string()),
+                    default_value: None,
+                    description: None,
+                    deprecation: None,
+                }],
+            },
+        );
+
+        let ft = FieldType::Named("Filter".to_string());
+        let value = input_default_value(
+            &serde_json::json!({"role": "ADMIN", "name": "x"}),
+            &ft,
+            &config,
+        );
+        let Value::Object(obj) = value else {
+            panic!("Expected object");
+        };
+        assert_eq!(obj.get("role"), Some(&Value::Enum(Name::new("ADMIN"))));
+        assert_eq!(obj.get("name"), Some(&Value::String("x".to_string())));
+    }
+
+    #[test]
+    fn test_input_default_value_scalar() {
+        let config = GraphQLConfig::default();
+        let ft = FieldType::Named("Int".to_string());
+        assert_eq!(
+            input_default_value(&serde_json::json!(10), &ft, &config),
+            Value::Number(10.into())
+        );
+    }
+
     #[test]
     fn test_schema_builder_new() {
         let config = GraphQLConfig::default();
@@ -165,7 +556,10 @@ mod tests {
                     field_type: FieldType::Named("String".to_string()),
                     arguments: vec![],
                     resolver: None,
+                    description: None,
+                    deprecation: None,
                 }],
+                ..Default::default()
             },
         );
 
@@ -194,9 +588,14 @@ mod tests {
                         name: "id".to_string(),
                         arg_type: FieldType::NonNull(Box::new(FieldType::Named("ID".to_string()))),
                         default_value: None,
+                        description: None,
+                        deprecation: None,
                     }],
                     resolver: None,
+                    description: None,
+                    deprecation: None,
                 }],
+                ..Default::default()
             },
         );
         config.types.insert(
@@ -208,7 +607,10 @@ mod tests {
                     field_type: FieldType::Named("ID".to_string()),
                     arguments: vec![],
                     resolver: None,
+                    description: None,
+                    deprecation: None,
                 }],
+                ..Default::default()
             },
         );
 
@@ -218,6 +620,73 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_schema_builder_with_mutation_type() {
+        use crate::config::TypeConfig;
+
+        let mut config = GraphQLConfig {
+            query_type: Some("Query".to_string()),
+            mutation_type: Some("Mutation".to_string()),
+            ..Default::default()
+        };
+        for type_name in ["Query", "Mutation"] {
+            config.types.insert(
+                type_name.to_string(),
+                TypeConfig {
+                    name: type_name.to_string(),
+                    fields: vec![FieldConfig {
+                        name: "hello".to_string(),
+                        field_type: FieldType::Named("String".to_string()),
+                        arguments: vec![],
+                        resolver: None,
+                        description: None,
+                        deprecation: None,
+                    }],
+                    ..Default::default()
+                },
+            );
+        }
+
+        let registry = Arc::new(TraitRegistry::default());
+        let schema = SchemaBuilder::new(config, registry).build().unwrap();
+        assert!(schema.sdl().contains("mutation: Mutation"));
+    }
+
+    #[test]
+    fn test_schema_builder_subscription_field_requires_resolver() {
+        use crate::config::TypeConfig;
+
+        let mut config = GraphQLConfig {
+            query_type: Some("Query".to_string()),
+            subscription_type: Some("Subscription".to_string()),
+            ..Default::default()
+        };
+        for type_name in ["Query", "Subscription"] {
+            config.types.insert(
+                type_name.to_string(),
+                TypeConfig {
+                    name: type_name.to_string(),
+                    fields: vec![FieldConfig {
+                        name: "ticks".to_string(),
+                        field_type: FieldType::Named("Int".to_string()),
+                        arguments: vec![],
+                        resolver: None,
+                        description: None,
+                        deprecation: None,
+                    }],
+                    ..Default::default()
+                },
+            );
+        }
+
+        let registry = Arc::new(TraitRegistry::default());
+        let result = SchemaBuilder::new(config, registry).build();
+        match result {
+            Err(ResolverError::Execution(msg)) => assert!(msg.contains("Subscription.ticks")),
+            _ => panic!("Expected Execution error"),
+        }
+    }
+
     #[test]
     fn test_schema_builder_default_query_type() {
         use crate::config::TypeConfig;
@@ -232,7 +701,10 @@ mod tests {
                     field_type: FieldType::Named("String".to_string()),
                     arguments: vec![],
                     resolver: None,
+                    description: None,
+                    deprecation: None,
                 }],
+                ..Default::default()
             },
         );
 
@@ -241,4 +713,74 @@ mod tests {
         let result = builder.build();
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_schema_builder_interfaces_and_unions() {
+        use crate::config::{ObjectKind, TypeConfig, UnionConfig};
+
+        let id_field = FieldConfig {
+            name: "id".to_string(),
+            field_type: FieldType::NonNull(Box::new(FieldType::Named("ID".to_string()))),
+            arguments: vec![],
+            resolver: None,
+            description: None,
+            deprecation: None,
+        };
+        let mut config = GraphQLConfig {
+            query_type: Some("Query".to_string()),
+            ..Default::default()
+        };
+        config.types.insert(
+            "Query".to_string(),
+            TypeConfig {
+                name: "Query".to_string(),
+                fields: vec![FieldConfig {
+                    name: "search".to_string(),
+                    field_type: FieldType::Named("SearchResult".to_string()),
+                    arguments: vec![],
+                    resolver: None,
+                    description: None,
+                    deprecation: None,
+                }],
+                ..Default::default()
+            },
+        );
+        config.types.insert(
+            "Node".to_string(),
+            TypeConfig {
+                name: "Node".to_string(),
+                description: None,
+                fields: vec![id_field.clone()],
+                kind: ObjectKind::Interface {
+                    discriminator: None,
+                },
+                implements: vec![],
+            },
+        );
+        config.types.insert(
+            "User".to_string(),
+            TypeConfig {
+                name: "User".to_string(),
+                description: None,
+                fields: vec![id_field],
+                kind: ObjectKind::Object,
+                implements: vec!["Node".to_string()],
+            },
+        );
+        config.unions.insert(
+            "SearchResult".to_string(),
+            UnionConfig {
+                name: "SearchResult".to_string(),
+                description: None,
+                members: vec!["User".to_string()],
+                discriminator: None,
+            },
+        );
+
+        let registry = Arc::new(TraitRegistry::default());
+        let sdl = SchemaBuilder::new(config, registry).build().unwrap().sdl();
+        assert!(sdl.contains("interface Node"));
+        assert!(sdl.contains("type User implements Node"));
+        assert!(sdl.contains("union SearchResult = User"));
+    }
 }
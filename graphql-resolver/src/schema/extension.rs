@@ -0,0 +1,128 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_graphql::extensions::{
+    Extension, ExtensionContext, ExtensionFactory, NextPrepareRequest,
+};
+use async_graphql::{Data, Request, ServerResult};
+
+use crate::batch::BatchLoaders;
+use crate::registry::resolver::ContextData;
+use crate::registry::storage::TraitRegistry;
+
+#[derive(Clone)]
+pub(crate) struct RequestExtension {
+    registry: Arc<TraitRegistry>,
+    shared_data: Arc<Data>,
+    delay: Duration,
+    max_batch_size: usize,
+}
+
+impl RequestExtension {
+    pub fn new(
+        registry: Arc<TraitRegistry>,
+        shared_data: Arc<Data>,
+        delay: Duration,
+        max_batch_size: usize,
+    ) -> Self {
+        Self {
+            registry,
+            shared_data,
+            delay,
+            max_batch_size,
+        }
+    }
+}
+
+impl ExtensionFactory for RequestExtension {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(self.clone())
+    }
+}
+
+#[async_trait::async_trait]
+impl Extension for RequestExtension {
+    async fn prepare_request(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        request: Request,
+        next: NextPrepareRequest<'_>,
+    ) -> ServerResult<Request> {
+        // The request data stays on the request; resolvers read it from the
+        // query environment once async-graphql has moved it there.
+        let data = ContextData::new(Arc::default(), self.shared_data.clone());
+        let loaders = BatchLoaders::new(self.registry.clone(), self.delay, self.max_batch_size);
+        next.run(ctx, request.data(loaders).data(data)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_graphql::dynamic::{Field, FieldFuture, Object, Schema, TypeRef};
+    use async_graphql::extensions::NextExecute;
+    use async_graphql::Response;
+    use std::sync::Mutex;
+
+    struct CurrentUser(String);
+
+    #[derive(Clone, Default)]
+    struct RecordingExtension {
+        seen: Arc<Mutex<Option<String>>>,
+    }
+
+    impl ExtensionFactory for RecordingExtension {
+        fn create(&self) -> Arc<dyn Extension> {
+            Arc::new(self.clone())
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl Extension for RecordingExtension {
+        async fn execute(
+            &self,
+            ctx: &ExtensionContext<'_>,
+            operation_name: Option<&str>,
+            next: NextExecute<'_>,
+        ) -> Response {
+            *self.seen.lock().unwrap() = ctx.data_opt::<CurrentUser>().map(|user| user.0.clone());
+            next.run(ctx, operation_name).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_request_data_stays_visible_to_other_extensions() {
+        let recording = RecordingExtension::default();
+        let query = Object::new("Query").field(Field::new(
+            "viewer",
+            TypeRef::named(TypeRef::STRING),
+            |ctx| {
+                FieldFuture::new(async move {
+                    let user = ctx.data::<CurrentUser>()?;
+                    Ok(Some(async_graphql::Value::from(user.0.clone())))
+                })
+            },
+        ));
+        let schema = Schema::build("Query", None, None)
+            .register(query)
+            .extension(RequestExtension::new(
+                Arc::new(TraitRegistry::new()),
+                Arc::default(),
+                Duration::from_millis(1),
+                100,
+            ))
+            .extension(recording.clone())
+            .finish()
+            .unwrap();
+
+        let request = Request::new("{ viewer }").data(CurrentUser("alice".to_string()));
+        let response = schema.execute(request).await;
+
+        assert!(response.errors.is_empty(), "Errors: {:?}", response.errors);
+        assert_eq!(
+            response.data.into_json().unwrap(),
+            serde_json::json!({ "viewer": "alice" })
+        );
+        assert_eq!(recording.seen.lock().unwrap().as_deref(), Some("alice"));
+    }
+}
@@ -1,13 +1,20 @@
 use rustc_hash::FxHashMap;
 use std::sync::Arc;
 
-use async_graphql::dynamic::{Field, FieldFuture, FieldValue, TypeRef};
+use async_graphql::dynamic::{
+    Field, FieldFuture, FieldValue, SubscriptionField, SubscriptionFieldFuture, TypeRef,
+};
 use async_graphql::Value;
+use futures::StreamExt;
 
-use crate::config::{ArgumentMapping, FieldConfig, ResolverConfig};
-use crate::error::ResolverError;
-use crate::registry::resolver::ResolverContext;
+use crate::batch::BatchLoaders;
+use crate::config::{ArgumentMapping, FieldConfig, FieldType, ResolverConfig};
+use crate::error::{ErrorMapper, ResolverError};
+use crate::registry::resolver::{ContextData, ResolverContext};
+use crate::registry::selection::{collect_selection, SelectedField};
 use crate::registry::storage::TraitRegistry;
+use crate::schema::input::InputCoercion;
+use crate::schema::output::OutputCoercion;
 
 pub(crate) fn value_to_field_value(value: Value) -> FieldValue<'static> {
     match value {
@@ -17,10 +24,64 @@ pub(crate) fn value_to_field_value(value: Value) -> FieldValue<'static> {
     }
 }
 
+fn context_data(ctx: &async_graphql::dynamic::ResolverContext<'_>) -> ContextData {
+    ctx.data_opt::<ContextData>()
+        .cloned()
+        .unwrap_or_default()
+        .with_request(ctx.ctx.query_env.query_data.clone())
+}
+
+fn selection(ctx: &async_graphql::dynamic::ResolverContext<'_>) -> Vec<SelectedField> {
+    collect_selection(ctx.field().selection_set())
+}
+
+fn clear_batch_cache(ctx: &async_graphql::dynamic::ResolverContext<'_>) {
+    if let Some(loaders) = ctx.data_opt::<BatchLoaders>() {
+        loaders.clear();
+    }
+}
+
+fn field_result(
+    ctx: &async_graphql::dynamic::ResolverContext<'_>,
+    errors: &ErrorMapper,
+    output_coercion: &OutputCoercion,
+    field_type: &FieldType,
+    result: Result<Option<Value>, Arc<ResolverError>>,
+) -> async_graphql::Result<Option<FieldValue<'static>>> {
+    let result = result.and_then(|value| {
+        value
+            .map(|value| output_coercion.field_value(value, field_type))
+            .transpose()
+            .map_err(Arc::new)
+    });
+    let err = match result {
+        Ok(value) => return Ok(value),
+        Err(err) => err,
+    };
+
+    let mapped = errors.map_error(&err);
+    let err = mapped.as_ref().unwrap_or(&err);
+    if err.data().is_none() && matches!(field_type, FieldType::NonNull(_)) {
+        return Err(err.to_graphql_error());
+    }
+
+    let value = err
+        .data()
+        .map(|data| output_coercion.field_value(data.clone(), field_type))
+        .transpose()
+        .map_err(|e| errors.to_graphql_error(&e))?;
+    ctx.add_error(ctx.set_error_path(err.to_graphql_error().into_server_error(ctx.item.pos)));
+    Ok(value)
+}
+
 pub(crate) struct FieldResolverFactory {
     parent_type: String,
     field_config: FieldConfig,
     registry: Arc<TraitRegistry>,
+    input_coercion: Arc<InputCoercion>,
+    output_coercion: Arc<OutputCoercion>,
+    errors: ErrorMapper,
+    mutation_root: bool,
 }
 
 impl FieldResolverFactory {
@@ -33,9 +94,33 @@ impl FieldResolverFactory {
             parent_type,
             field_config,
             registry,
+            input_coercion: Arc::default(),
+            output_coercion: Arc::default(),
+            errors: ErrorMapper::default(),
+            mutation_root: false,
         }
     }
 
+    pub fn with_input_coercion(mut self, input_coercion: Arc<InputCoercion>) -> Self {
+        self.input_coercion = input_coercion;
+        self
+    }
+
+    pub fn with_output_coercion(mut self, output_coercion: Arc<OutputCoercion>) -> Self {
+        self.output_coercion = output_coercion;
+        self
+    }
+
+    pub fn with_error_mapper(mut self, errors: ErrorMapper) -> Self {
+        self.errors = errors;
+        self
+    }
+
+    pub fn with_mutation_root(mut self, mutation_root: bool) -> Self {
+        self.mutation_root = mutation_root;
+        self
+    }
+
     pub fn create_field(self, type_ref: TypeRef) -> Result<Field, ResolverError> {
         let resolver_config = self
             .field_config
@@ -67,6 +152,76 @@ impl FieldResolverFactory {
         }
     }
 
+    pub fn create_subscription_field(
+        self,
+        type_ref: TypeRef,
+    ) -> Result<SubscriptionField, ResolverError> {
+        let resolver_name = match &self.field_config.resolver {
+            Some(ResolverConfig::Trait {
+                name,
+                batch_key: None,
+            }) => name.clone(),
+            _ => {
+                return Err(ResolverError::Execution(format!(
+                    "Subscription field '{}.{}' requires a @resolver directive without @batchKey",
+                    self.parent_type, self.field_config.name
+                )))
+            }
+        };
+
+        let field_name = self.field_config.name.clone();
+        let parent_type = self.parent_type.clone();
+        let registry = self.registry.clone();
+
+        let input_coercion = self.input_coercion.clone();
+        let output_coercion = self.output_coercion.clone();
+        let errors = self.errors.clone();
+        let field_type = self.field_config.field_type.clone();
+        let arguments = self.field_config.arguments.clone();
+        let field_name_for_new = field_name.clone();
+        Ok(SubscriptionField::new(
+            field_name_for_new,
+            type_ref,
+            move |ctx| {
+                let resolver_name = resolver_name.clone();
+                let field_name = field_name.clone();
+                let parent_type = parent_type.clone();
+                let registry = registry.clone();
+                let input_coercion = input_coercion.clone();
+                let output_coercion = output_coercion.clone();
+                let errors = errors.clone();
+                let field_type = field_type.clone();
+                let arguments = arguments.clone();
+
+                SubscriptionFieldFuture::new(async move {
+                    let stream = async {
+                        let resolver = registry.get_subscription_resolver(&resolver_name)?;
+
+                        let args = input_coercion.collect_args(&ctx, &arguments)?;
+
+                        let resolver_ctx = ResolverContext::new(field_name.clone())
+                            .with_path(vec![parent_type, field_name])
+                            .with_selection(selection(&ctx))
+                            .with_data(context_data(&ctx));
+
+                        resolver.subscribe(&resolver_ctx, args).await
+                    }
+                    .await
+                    .map_err(|e| errors.to_graphql_error(&e))?;
+                    let loaders = ctx.data_opt::<BatchLoaders>();
+
+                    Ok(stream.map(move |item| {
+                        if let Some(loaders) = loaders {
+                            loaders.clear();
+                        }
+                        item.and_then(|value| output_coercion.field_value(value, &field_type))
+                            .map_err(|e| errors.to_graphql_error(&e))
+                    }))
+                })
+            },
+        ))
+    }
+
     fn create_trait_field(
         self,
         type_ref: TypeRef,
@@ -76,23 +231,54 @@ impl FieldResolverFactory {
         parent_type: String,
         registry: Arc<TraitRegistry>,
     ) -> Result<Field, ResolverError> {
+        let mutation_root = self.mutation_root;
+        let input_coercion = self.input_coercion.clone();
+        let output_coercion = self.output_coercion.clone();
+        let errors = self.errors.clone();
+        let field_type = self.field_config.field_type.clone();
+        let arguments = self.field_config.arguments.clone();
         let field_name_for_new = field_name.clone();
-        let mut field = Field::new(field_name_for_new, type_ref, move |ctx| {
+        Ok(Field::new(field_name_for_new, type_ref, move |ctx| {
             let resolver_name = resolver_name.clone();
             let field_name = field_name.clone();
             let parent_type = parent_type.clone();
             let registry = registry.clone();
+            let input_coercion = input_coercion.clone();
+            let output_coercion = output_coercion.clone();
+            let errors = errors.clone();
+            let field_type = field_type.clone();
+            let arguments = arguments.clone();
             let batch_key = batch_key.clone();
 
             FieldFuture::new(async move {
+                if mutation_root {
+                    clear_batch_cache(&ctx);
+                }
+
                 let parent = ctx
                     .parent_value
                     .try_downcast_ref::<Value>()
                     .cloned()
                     .unwrap_or(Value::Null);
 
-                if let Some(ref key_field) = batch_key {
-                    let batch_resolver = registry.get_batch_resolver(&resolver_name)?;
+                let result = async {
+                    let Some(ref key_field) = batch_key else {
+                        let resolver = registry.get_resolver(&resolver_name)?;
+
+                        let args = input_coercion.collect_args(&ctx, &arguments)?;
+
+                        let resolver_ctx = ResolverContext::new(field_name.clone())
+                            .with_parent(parent)
+                            .with_path(vec![parent_type, field_name])
+                            .with_selection(selection(&ctx))
+                            .with_data(context_data(&ctx));
+
+                        return Ok(Some(resolver.resolve(&resolver_ctx, args).await?));
+                    };
+
+                    let loaders = ctx.data_opt::<BatchLoaders>().ok_or_else(|| {
+                        ResolverError::Execution("Batch loaders are not available".to_string())
+                    })?;
 
                     let key_value = if let Value::Object(obj) = &parent {
                         obj.get(key_field.as_str())
@@ -102,54 +288,31 @@ impl FieldResolverFactory {
                     } else {
                         serde_json::Value::Null
                     };
-
-                    let resolver_ctx = ResolverContext::new(field_name.clone())
-                        .with_parent(parent)
-                        .with_path(vec![parent_type, field_name]);
-
-                    let results = batch_resolver
-                        .load_erased(&resolver_ctx, vec![key_value.clone()])
-                        .await?;
-
-                    let result = results
-                        .into_iter()
-                        .find(|(k, _)| k == &key_value)
-                        .map(|(_, v)| v);
-
-                    match result {
-                        Some(json_val) => {
-                            let gql_val: Value =
-                                serde_json::from_value(json_val).unwrap_or(Value::Null);
-                            Ok(Some(value_to_field_value(gql_val)))
-                        }
-                        None => Ok(None),
-                    }
-                } else {
-                    let resolver = registry.get_resolver(&resolver_name)?;
-
-                    let mut args = FxHashMap::default();
-                    for (name, value) in ctx.args.iter() {
-                        if let Ok(gql_value) = value.deserialize::<Value>() {
-                            args.insert(name.to_string(), gql_value);
-                        }
+                    // A missing or null key, such as an unset foreign key, has
+                    // nothing to load.
+                    if key_value.is_null() {
+                        return Ok(None);
                     }
 
-                    let resolver_ctx = ResolverContext::new(field_name.clone())
-                        .with_parent(parent)
-                        .with_path(vec![parent_type, field_name]);
+                    let path = vec![parent_type, field_name.clone()];
+                    let result = loaders
+                        .load(
+                            &resolver_name,
+                            &path,
+                            &selection(&ctx),
+                            &key_value,
+                            &context_data(&ctx),
+                        )
+                        .await?;
 
-                    let result = resolver.resolve(&resolver_ctx, args).await?;
-                    Ok(Some(value_to_field_value(result)))
+                    Ok(result
+                        .map(|json_val| serde_json::from_value(json_val).unwrap_or(Value::Null)))
                 }
-            })
-        });
+                .await;
 
-        for arg in &self.field_config.arguments {
-            let arg_type = super::builder::convert_field_type(&arg.arg_type);
-            field = field.argument(async_graphql::dynamic::InputValue::new(&arg.name, arg_type));
-        }
-
-        Ok(field)
+                field_result(&ctx, &errors, &output_coercion, &field_type, result)
+            })
+        }))
     }
 
     fn create_call_field(
@@ -161,16 +324,29 @@ impl FieldResolverFactory {
         parent_type: String,
         registry: Arc<TraitRegistry>,
     ) -> Result<Field, ResolverError> {
+        let mutation_root = self.mutation_root;
+        let input_coercion = self.input_coercion.clone();
+        let output_coercion = self.output_coercion.clone();
+        let errors = self.errors.clone();
+        let field_type = self.field_config.field_type.clone();
+        let arguments = self.field_config.arguments.clone();
         let field_name_for_new = field_name.clone();
-        let mut field = Field::new(field_name_for_new, type_ref, move |ctx| {
+        Ok(Field::new(field_name_for_new, type_ref, move |ctx| {
             let trait_name = trait_name.clone();
             let arg_mappings = arg_mappings.clone();
             let field_name = field_name.clone();
             let parent_type = parent_type.clone();
             let registry = registry.clone();
+            let input_coercion = input_coercion.clone();
+            let output_coercion = output_coercion.clone();
+            let errors = errors.clone();
+            let field_type = field_type.clone();
+            let arguments = arguments.clone();
 
             FieldFuture::new(async move {
-                let resolver = registry.get_resolver(&trait_name)?;
+                if mutation_root {
+                    clear_batch_cache(&ctx);
+                }
 
                 let parent = ctx
                     .parent_value
@@ -178,43 +354,43 @@ impl FieldResolverFactory {
                     .cloned()
                     .unwrap_or(Value::Null);
 
-                let mut args = FxHashMap::default();
+                let result = async {
+                    let resolver = registry.get_resolver(&trait_name)?;
 
-                for (arg_name, mapping) in &arg_mappings {
-                    let value = match mapping {
-                        ArgumentMapping::ParentField(field) => {
-                            if let Value::Object(obj) = &parent {
-                                obj.get(field.as_str()).cloned().unwrap_or(Value::Null)
-                            } else {
-                                Value::Null
-                            }
-                        }
-                        ArgumentMapping::Argument(name) => ctx
-                            .args
-                            .get(name)
-                            .and_then(|v| v.deserialize::<Value>().ok())
-                            .unwrap_or(Value::Null),
-                        ArgumentMapping::Literal(json_val) => {
-                            serde_json::from_value(json_val.clone()).unwrap_or(Value::Null)
-                        }
-                    };
-                    args.insert(arg_name.clone(), value);
-                }
+                    let field_args = input_coercion.collect_args(&ctx, &arguments)?;
+                    let mut args = FxHashMap::default();
 
-                let resolver_ctx = ResolverContext::new(field_name.clone())
-                    .with_parent(parent)
-                    .with_path(vec![parent_type, field_name]);
+                    for (arg_name, mapping) in &arg_mappings {
+                        let value = match mapping {
+                            ArgumentMapping::ParentField(field) => {
+                                if let Value::Object(obj) = &parent {
+                                    obj.get(field.as_str()).cloned().unwrap_or(Value::Null)
+                                } else {
+                                    Value::Null
+                                }
+                            }
+                            ArgumentMapping::Argument(name) => {
+                                field_args.get(name).cloned().unwrap_or(Value::Null)
+                            }
+                            ArgumentMapping::Literal(json_val) => {
+                                serde_json::from_value(json_val.clone()).unwrap_or(Value::Null)
+                            }
+                        };
+                        args.insert(arg_name.clone(), value);
+                    }
 
-                let result = resolver.resolve(&resolver_ctx, args).await?;
-                Ok(Some(value_to_field_value(result)))
-            })
-        });
+                    let resolver_ctx = ResolverContext::new(field_name.clone())
+                        .with_parent(parent)
+                        .with_path(vec![parent_type, field_name])
+                        .with_selection(selection(&ctx))
+                        .with_data(context_data(&ctx));
 
-        for arg in &self.field_config.arguments {
-            let arg_type = super::builder::convert_field_type(&arg.arg_type);
-            field = field.argument(async_graphql::dynamic::InputValue::new(&arg.name, arg_type));
-        }
+                    Ok(Some(resolver.resolve(&resolver_ctx, args).await?))
+                }
+                .await;
 
-        Ok(field)
+                field_result(&ctx, &errors, &output_coercion, &field_type, result)
+            })
+        }))
     }
 }
@@ -0,0 +1,357 @@
+use std::sync::Arc;
+
+use async_graphql::dynamic::ResolverContext;
+use async_graphql::{Name, Value};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::config::{ArgumentConfig, FieldType, GraphQLConfig};
+use crate::registry::resolver::ResolverResult;
+use crate::registry::storage::TraitRegistry;
+use crate::scalar::ScalarCodec;
+use crate::schema::builder::input_default_value;
+
+struct InputFieldDefault {
+    name: String,
+    field_type: FieldType,
+    default_value: Option<Value>,
+}
+
+#[derive(Default)]
+pub(crate) struct InputCoercion {
+    types: FxHashMap<String, Vec<InputFieldDefault>>,
+    enums: FxHashSet<String>,
+    scalars: FxHashMap<String, Arc<dyn ScalarCodec>>,
+}
+
+impl InputCoercion {
+    pub fn from_config(config: &GraphQLConfig, registry: &TraitRegistry) -> Self {
+        let types = config
+            .input_types
+            .values()
+            .map(|input_config| {
+                let fields = input_config
+                    .fields
+                    .iter()
+                    .map(|field| InputFieldDefault {
+                        name: field.name.clone(),
+                        field_type: field.arg_type.clone(),
+                        default_value: field
+                            .default_value
+                            .as_ref()
+                            .map(|v| input_default_value(v, &field.arg_type, config)),
+                    })
+                    .collect();
+                (input_config.name.clone(), fields)
+            })
+            .collect();
+
+        let scalars = config
+            .scalars
+            .keys()
+            .filter_map(|name| {
+                registry
+                    .get_scalar_codec(name)
+                    .map(|codec| (name.clone(), codec))
+            })
+            .collect();
+
+        Self {
+            types,
+            enums: config.enums.keys().cloned().collect(),
+            scalars,
+        }
+    }
+
+    pub fn coerce(&self, value: Value, field_type: &FieldType) -> ResolverResult<Value> {
+        match (field_type, value) {
+            (_, Value::Null) => Ok(Value::Null),
+            (FieldType::NonNull(inner), value) => self.coerce(value, inner),
+            (FieldType::List(inner), Value::List(items)) => items
+                .into_iter()
+                .map(|item| self.coerce(item, inner))
+                .collect::<ResolverResult<_>>()
+                .map(Value::List),
+            (FieldType::List(inner), value) => self.coerce(value, inner),
+            (FieldType::Named(name), Value::String(s)) if self.enums.contains(name) => {
+                Ok(Value::Enum(Name::new(s)))
+            }
+            (FieldType::Named(name), value) => match self.scalars.get(name) {
+                Some(codec) => codec.parse(value),
+                None => self.coerce_input_object(name, value),
+            },
+        }
+    }
+
+    fn coerce_input_object(&self, type_name: &str, value: Value) -> ResolverResult<Value> {
+        let Some(fields) = self.types.get(type_name) else {
+            return Ok(value);
+        };
+        let mut obj = match value {
+            Value::Object(obj) => obj,
+            value => return Ok(value),
+        };
+
+        for field in fields {
+            let key = Name::new(&field.name);
+            match obj.get_mut(&key) {
+                Some(item) => {
+                    *item = self.coerce(std::mem::take(item), &field.field_type)?;
+                }
+                None => {
+                    if let Some(default_value) = &field.default_value {
+                        obj.insert(key, default_value.clone());
+                    }
+                }
+            }
+        }
+
+        Ok(Value::Object(obj))
+    }
+
+    pub fn collect_args(
+        &self,
+        ctx: &ResolverContext<'_>,
+        arguments: &[ArgumentConfig],
+    ) -> ResolverResult<FxHashMap<String, Value>> {
+        let omitted = omitted_variable_arguments(ctx);
+        let mut collected = FxHashMap::default();
+        for (name, value) in ctx.args.iter() {
+            if omitted.contains(name.as_str()) {
+                continue;
+            }
+            if let Ok(gql_value) = value.deserialize::<Value>() {
+                let gql_value = match arguments.iter().find(|a| a.name == name.as_str()) {
+                    Some(arg) => self.coerce(gql_value, &arg.arg_type)?,
+                    None => gql_value,
+                };
+                collected.insert(name.to_string(), gql_value);
+            }
+        }
+
+        for arg in arguments {
+            if collected.contains_key(&arg.name) {
+                continue;
+            }
+            if let Some(default_value) = &arg.default_value {
+                let value = self.coerce(Value::from_json(default_value.clone())?, &arg.arg_type)?;
+                collected.insert(arg.name.clone(), value);
+            }
+        }
+        Ok(collected)
+    }
+}
+
+// An argument bound to a variable the request did not provide counts as
+// omitted, so its SDL default applies instead of null.
+fn omitted_variable_arguments<'a>(ctx: &'a ResolverContext<'_>) -> FxHashSet<&'a str> {
+    let env = &ctx.query_env;
+    let definitions = &env.operation.node.variable_definitions;
+    ctx.item
+        .node
+        .arguments
+        .iter()
+        .filter(|(_, value)| {
+            let mut omitted = false;
+            let resolved = value.node.clone().into_const_with(|name| {
+                omitted |= !env.variables.contains_key(&name)
+                    && !definitions
+                        .iter()
+                        .any(|def| def.node.name.node == name && def.node.default_value.is_some());
+                Ok::<_, ()>(Value::Null)
+            });
+            omitted && resolved == Ok(Value::Null)
+        })
+        .map(|(name, _)| name.node.as_str())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::InputObjectConfig;
+
+    fn make_coercion() -> InputCoercion {
+        let mut config = GraphQLConfig::default();
+        config.input_types.insert(
+            "Page".to_string(),
+            InputObjectConfig {
+                name: "Page".to_string(),
+                description: None,
+                fields: vec![ArgumentConfig {
+                    name: "size".to_string(),
+                    arg_type: FieldType::Named("Int".to_string()),
+                    default_value: Some(serde_json::json!(25)),
+                    description: None,
+                    deprecation: None,
+                }],
+            },
+        );
+        config.input_types.insert(
+            "Filter".to_string(),
+            InputObjectConfig {
+                name: "Filter".to_string(),
+                description: None,
+                fields: vec![
+                    ArgumentConfig {
+                        name: "name".to_string(),
+                        arg_type: FieldType::Named("String".to_string()),
+                        default_value: None,
+                        description: None,
+                        deprecation: None,
+                    },
+                    ArgumentConfig {
+                        name: "page".to_string(),
+                        arg_type: FieldType::Named("Page".to_string()),
+                        default_value: None,
+                        description: None,
+                        deprecation: None,
+                    },
+                ],
+            },
+        );
+        InputCoercion::from_config(&config, &TraitRegistry::default())
+    }
+
+    fn object(json: serde_json::Value) -> Value {
+        Value::from_json(json).unwrap()
+    }
+
+    #[test]
+    fn test_coerce_fills_missing_fields() {
+        let coercion = make_coercion();
+        let value = coercion
+            .coerce(
+                object(serde_json::json!({})),
+                &FieldType::Named("Page".to_string()),
+            )
+            .unwrap();
+        assert_eq!(value, object(serde_json::json!({"size": 25})));
+    }
+
+    #[test]
+    fn test_coerce_keeps_provided_fields() {
+        let coercion = make_coercion();
+        let value = coercion
+            .coerce(
+                object(serde_json::json!({"size": 5})),
+                &FieldType::Named("Page".to_string()),
+            )
+            .unwrap();
+        assert_eq!(value, object(serde_json::json!({"size": 5})));
+    }
+
+    #[test]
+    fn test_coerce_nested_and_lists() {
+        let coercion = make_coercion();
+        let ft = FieldType::NonNull(Box::new(FieldType::List(Box::new(FieldType::Named(
+            "Filter".to_string(),
+        )))));
+        let value = coercion
+            .coerce(
+                object(serde_json::json!([{"name": "a", "page": {}}, {"name": "b"}])),
+                &ft,
+            )
+            .unwrap();
+        assert_eq!(
+            value,
+            object(serde_json::json!([
+                {"name": "a", "page": {"size": 25}},
+                {"name": "b"}
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_coerce_ignores_unknown_types() {
+        let coercion = InputCoercion::default();
+        let value = coercion
+            .coerce(
+                Value::Number(1.into()),
+                &FieldType::Named("Int".to_string()),
+            )
+            .unwrap();
+        assert_eq!(value, Value::Number(1.into()));
+    }
+
+    #[test]
+    fn test_coerce_enum_strings() {
+        let mut config = GraphQLConfig::default();
+        config.enums.insert(
+            "Role".to_string(),
+            crate::config::EnumConfig {
+                name: "Role".to_string(),
+                description: None,
+                values: Vec::new(),
+            },
+        );
+        let coercion = InputCoercion::from_config(&config, &TraitRegistry::default());
+        let value = coercion
+            .coerce(
+                Value::String("ADMIN".to_string()),
+                &FieldType::Named("Role".to_string()),
+            )
+            .unwrap();
+        assert_eq!(value, Value::Enum(Name::new("ADMIN")));
+    }
+
+    struct UpperCodec;
+
+    impl ScalarCodec for UpperCodec {
+        fn name(&self) -> &'static str {
+            "Upper"
+        }
+
+        fn parse(&self, value: Value) -> ResolverResult<Value> {
+            match value {
+                Value::String(s) => Ok(Value::String(s.to_uppercase())),
+                _ => Err(crate::error::ResolverError::Argument(
+                    "expected a string".to_string(),
+                )),
+            }
+        }
+    }
+
+    #[test]
+    fn test_coerce_scalar_codecs() {
+        let mut config = GraphQLConfig::default();
+        config.scalars.insert(
+            "Upper".to_string(),
+            crate::config::ScalarConfig {
+                name: "Upper".to_string(),
+                description: None,
+            },
+        );
+        config.input_types.insert(
+            "Tag".to_string(),
+            InputObjectConfig {
+                name: "Tag".to_string(),
+                description: None,
+                fields: vec![ArgumentConfig {
+                    name: "label".to_string(),
+                    arg_type: FieldType::Named("Upper".to_string()),
+                    default_value: None,
+                    description: None,
+                    deprecation: None,
+                }],
+            },
+        );
+        let mut registry = TraitRegistry::default();
+        registry.register_scalar_codec(UpperCodec);
+        let coercion = InputCoercion::from_config(&config, &registry);
+
+        let ft = FieldType::List(Box::new(FieldType::Named("Tag".to_string())));
+        let value = coercion
+            .coerce(
+                object(serde_json::json!([{"label": "a"}, {"label": null}])),
+                &ft,
+            )
+            .unwrap();
+        assert_eq!(
+            value,
+            object(serde_json::json!([{"label": "A"}, {"label": null}]))
+        );
+
+        let result = coercion.coerce(Value::Boolean(true), &FieldType::Named("Upper".to_string()));
+        assert!(result.is_err());
+    }
+}
@@ -1,4 +1,7 @@
 mod builder;
+mod extension;
 mod field_resolver;
+mod input;
+mod output;
 
 pub(crate) use builder::SchemaBuilder;
@@ -0,0 +1,195 @@
+use std::sync::Arc;
+
+use async_graphql::dynamic::FieldValue;
+use async_graphql::Value;
+use rustc_hash::FxHashMap;
+
+use crate::config::{FieldType, GraphQLConfig};
+use crate::error::ResolverError;
+use crate::registry::storage::TraitRegistry;
+use crate::scalar::ScalarCodec;
+use crate::schema::field_resolver::value_to_field_value;
+
+#[derive(Default)]
+pub(crate) struct OutputCoercion {
+    types: FxHashMap<String, String>,
+    scalars: FxHashMap<String, Option<Arc<dyn ScalarCodec>>>,
+}
+
+impl OutputCoercion {
+    pub fn from_config(config: &GraphQLConfig, registry: &TraitRegistry) -> Self {
+        let types = config
+            .types
+            .keys()
+            .chain(config.unions.keys())
+            .filter_map(|name| {
+                config
+                    .type_discriminator(name)
+                    .map(|field| (name.clone(), field.to_string()))
+            })
+            .collect();
+
+        let scalars = config
+            .scalars
+            .keys()
+            .map(|name| (name.clone(), registry.get_scalar_codec(name)))
+            .collect();
+
+        Self { types, scalars }
+    }
+
+    pub fn field_value(
+        &self,
+        value: Value,
+        field_type: &FieldType,
+    ) -> Result<FieldValue<'static>, ResolverError> {
+        match (field_type, value) {
+            (FieldType::Named(name), value) if self.scalars.contains_key(name) => {
+                let value = match (&self.scalars[name], value) {
+                    (Some(codec), value) if value != Value::Null => codec.serialize(value)?,
+                    (_, value) => value,
+                };
+                Ok(FieldValue::value(value))
+            }
+            (FieldType::NonNull(inner), value) => self.field_value(value, inner),
+            (FieldType::List(inner), Value::List(items)) => {
+                let items = items
+                    .into_iter()
+                    .map(|item| self.field_value(item, inner))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(FieldValue::list(items))
+            }
+            (FieldType::List(inner), value) => self.field_value(value, inner),
+            (FieldType::Named(name), Value::Object(obj)) => {
+                let Some(discriminator) = self.types.get(name) else {
+                    return Ok(value_to_field_value(Value::Object(obj)));
+                };
+
+                let concrete_type = match obj.get(discriminator.as_str()) {
+                    Some(Value::String(s)) => s.clone(),
+                    Some(Value::Enum(e)) => e.to_string(),
+                    _ => {
+                        return Err(ResolverError::Execution(format!(
+                            "Cannot resolve concrete type of '{}': value has no '{}' field",
+                            name, discriminator
+                        )))
+                    }
+                };
+
+                Ok(FieldValue::owned_any(Value::Object(obj)).with_type(concrete_type))
+            }
+            (_, value) => Ok(value_to_field_value(value)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ObjectKind, TypeConfig, UnionConfig};
+
+    fn make_coercion() -> OutputCoercion {
+        let mut config = GraphQLConfig::default();
+        config.types.insert(
+            "Node".to_string(),
+            TypeConfig {
+                name: "Node".to_string(),
+                kind: ObjectKind::Interface {
+                    discriminator: Some("kind".to_string()),
+                },
+                ..Default::default()
+            },
+        );
+        config.unions.insert(
+            "SearchResult".to_string(),
+            UnionConfig {
+                name: "SearchResult".to_string(),
+                description: None,
+                members: vec!["User".to_string()],
+                discriminator: None,
+            },
+        );
+        OutputCoercion::from_config(&config, &TraitRegistry::default())
+    }
+
+    fn object(json: serde_json::Value) -> Value {
+        Value::from_json(json).unwrap()
+    }
+
+    #[test]
+    fn test_field_value_uses_typename() {
+        let coercion = make_coercion();
+        let value = coercion
+            .field_value(
+                object(serde_json::json!({"__typename": "User"})),
+                &FieldType::Named("SearchResult".to_string()),
+            )
+            .unwrap();
+        assert_eq!(format!("{:?}", value), "User");
+    }
+
+    #[test]
+    fn test_field_value_uses_custom_discriminator_in_lists() {
+        let coercion = make_coercion();
+        let ft = FieldType::NonNull(Box::new(FieldType::List(Box::new(FieldType::Named(
+            "Node".to_string(),
+        )))));
+        let value = coercion
+            .field_value(object(serde_json::json!([{"kind": "User"}])), &ft)
+            .unwrap();
+        let items = value.as_list().unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(format!("{:?}", items[0]), "User");
+    }
+
+    #[test]
+    fn test_field_value_missing_discriminator() {
+        let coercion = make_coercion();
+        let result = coercion.field_value(
+            object(serde_json::json!({"__typename": "User"})),
+            &FieldType::Named("Node".to_string()),
+        );
+        match result {
+            Err(ResolverError::Execution(msg)) => {
+                assert!(msg.contains("'Node'"));
+                assert!(msg.contains("'kind'"));
+            }
+            _ => panic!("Expected Execution error"),
+        }
+    }
+
+    #[test]
+    fn test_field_value_concrete_types_untouched() {
+        let coercion = make_coercion();
+        let value = coercion
+            .field_value(
+                object(serde_json::json!({"name": "a"})),
+                &FieldType::Named("User".to_string()),
+            )
+            .unwrap();
+        assert!(value.downcast_ref::<Value>().is_some());
+    }
+
+    #[test]
+    fn test_field_value_keeps_custom_scalar_objects() {
+        let mut config = GraphQLConfig::default();
+        config.scalars.insert(
+            "JSON".to_string(),
+            crate::config::ScalarConfig {
+                name: "JSON".to_string(),
+                description: None,
+            },
+        );
+        let coercion = OutputCoercion::from_config(&config, &TraitRegistry::default());
+        let value = coercion
+            .field_value(
+                object(serde_json::json!({"a": [1, 2]})),
+                &FieldType::Named("JSON".to_string()),
+            )
+            .unwrap();
+        assert_eq!(
+            value.as_value(),
+            Some(&object(serde_json::json!({"a": [1, 2]})))
+        );
+    }
+}
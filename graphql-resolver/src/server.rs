@@ -1,33 +1,57 @@
+use std::any::Any;
+use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
 
 use async_graphql::dynamic::Schema;
+use async_graphql::{Data, Executor};
+use futures::stream::BoxStream;
 
-use crate::config::{parse_sdl, GraphQLConfig};
-use crate::error::ResolverError;
+use crate::config::{
+    find_sdl_files, parse_sdl_sources, GraphQLConfig, ParseError, SdlError, SdlSources,
+};
+use crate::error::{ErrorMapper, ResolverError};
 use crate::n1::{N1Detector, N1Error};
-use crate::registry::resolver::Resolver;
+use crate::registry::resolver::{Resolver, SubscriptionResolver};
 use crate::registry::storage::{ErasedBatchResolver, TraitRegistry};
+use crate::scalar::ScalarCodec;
 use crate::schema::SchemaBuilder;
+use crate::validation::{ResolverValidator, ValidationError};
 
 #[derive(Debug, thiserror::Error)]
 pub enum ServerError {
     #[error("Parse error: {0}")]
-    Parse(String),
+    Parse(SdlError),
+    #[error("Conflicting SDL definitions:{}", render_errors(.0))]
+    Conflict(Vec<SdlError>),
     #[error("Resolver error: {0}")]
     Resolver(#[from] ResolverError),
-    #[error("N+1 query detected")]
+    #[error("N+1 query detected:{}", render_errors(.0))]
     N1Detection(Vec<N1Error>),
+    #[error("Resolver validation failed:{}", render_errors(.0))]
+    Validation(Vec<ValidationError>),
     #[error("Configuration error: {0}")]
     Config(String),
 }
 
+// Puts each error, with its source snippet, on its own lines below the
+// summary, so a failed `build()?` at startup shows what to fix.
+fn render_errors<E: std::fmt::Display>(errors: &[E]) -> String {
+    errors.iter().map(|error| format!("\n{}", error)).collect()
+}
+
 pub struct GraphQLServerBuilder {
-    sdl_parts: Vec<String>,
+    sdl: SdlSources,
+    sdl_errors: Vec<String>,
     registry: TraitRegistry,
+    shared_data: Data,
+    errors: ErrorMapper,
     batch_delay: Duration,
     max_batch_size: usize,
     validate_n1: bool,
+    validate_resolvers: bool,
+    use_inventory: bool,
+    merge_types: bool,
 }
 
 impl Default for GraphQLServerBuilder {
@@ -39,16 +63,71 @@ impl Default for GraphQLServerBuilder {
 impl GraphQLServerBuilder {
     pub fn new() -> Self {
         Self {
-            sdl_parts: Vec::new(),
+            sdl: SdlSources::default(),
+            sdl_errors: Vec::new(),
             registry: TraitRegistry::new(),
+            shared_data: Data::default(),
+            errors: ErrorMapper::default(),
             batch_delay: Duration::from_millis(1),
             max_batch_size: 100,
             validate_n1: true,
+            validate_resolvers: true,
+            use_inventory: false,
+            merge_types: false,
         }
     }
 
     pub fn sdl(mut self, sdl: &str) -> Self {
-        self.sdl_parts.push(sdl.to_string());
+        let name = format!("<sdl {}>", self.sdl.len() + 1);
+        self.sdl.push(name, sdl);
+        self
+    }
+
+    pub fn sdl_named(mut self, name: &str, sdl: &str) -> Self {
+        self.sdl.push(name, sdl);
+        self
+    }
+
+    pub fn sdl_file(mut self, path: impl AsRef<Path>) -> Self {
+        self.push_sdl_file(path.as_ref());
+        self
+    }
+
+    pub fn sdl_dir(mut self, path: impl AsRef<Path>, pattern: &str) -> Self {
+        let path = path.as_ref();
+        match find_sdl_files(path, pattern) {
+            Ok(files) if files.is_empty() => self.sdl_errors.push(format!(
+                "No SDL files in '{}' match '{}'",
+                path.display(),
+                pattern
+            )),
+            Ok(files) => {
+                for file in files {
+                    self.push_sdl_file(&file);
+                }
+            }
+            Err(e) => self.sdl_errors.push(format!(
+                "Failed to read SDL directory '{}': {}",
+                path.display(),
+                e
+            )),
+        }
+        self
+    }
+
+    fn push_sdl_file(&mut self, path: &Path) {
+        match std::fs::read_to_string(path) {
+            Ok(sdl) => self.sdl.push(path.display().to_string(), sdl),
+            Err(e) => self.sdl_errors.push(format!(
+                "Failed to read SDL file '{}': {}",
+                path.display(),
+                e
+            )),
+        }
+    }
+
+    pub fn merge_types(mut self) -> Self {
+        self.merge_types = true;
         self
     }
 
@@ -65,6 +144,49 @@ impl GraphQLServerBuilder {
         self
     }
 
+    pub fn replace_resolver<R: Resolver>(mut self, resolver: R) -> Self {
+        self.registry.replace_resolver(resolver);
+        self
+    }
+
+    pub fn replace_batch_resolver<R: ErasedBatchResolver + 'static>(mut self, resolver: R) -> Self {
+        self.registry.replace_batch_resolver(resolver);
+        self
+    }
+
+    pub fn register_subscription_resolver<R: SubscriptionResolver>(mut self, resolver: R) -> Self {
+        self.registry.register_subscription_resolver(resolver);
+        self
+    }
+
+    pub fn replace_subscription_resolver<R: SubscriptionResolver>(mut self, resolver: R) -> Self {
+        self.registry.replace_subscription_resolver(resolver);
+        self
+    }
+
+    pub fn register_scalar_codec<C: ScalarCodec>(mut self, codec: C) -> Self {
+        self.registry.register_scalar_codec(codec);
+        self
+    }
+
+    pub fn with_inventory(mut self) -> Self {
+        self.use_inventory = true;
+        self
+    }
+
+    pub fn data<D: Any + Send + Sync>(mut self, data: D) -> Self {
+        self.shared_data.insert(data);
+        self
+    }
+
+    pub fn map_error<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&ResolverError) -> Option<ResolverError> + Send + Sync + 'static,
+    {
+        self.errors = ErrorMapper::new(hook);
+        self
+    }
+
     pub fn batch_delay(mut self, delay: Duration) -> Self {
         self.batch_delay = delay;
         self
@@ -80,24 +202,59 @@ impl GraphQLServerBuilder {
         self
     }
 
-    pub fn validate(self) -> Result<ValidatedServerBuilder, ServerError> {
-        if self.sdl_parts.is_empty() {
+    pub fn skip_resolver_validation(mut self) -> Self {
+        self.validate_resolvers = false;
+        self
+    }
+
+    pub fn validate(mut self) -> Result<ValidatedServerBuilder, ServerError> {
+        if !self.sdl_errors.is_empty() {
+            return Err(ServerError::Config(self.sdl_errors.join("; ")));
+        }
+
+        if self.sdl.is_empty() {
             return Err(ServerError::Config("SDL not provided".to_string()));
         }
 
-        let sdl = self.sdl_parts.join("\n");
-        let config = parse_sdl(&sdl).map_err(|e| ServerError::Parse(e.to_string()))?;
+        if !self.registry.duplicates().is_empty() {
+            return Err(ServerError::Config(self.registry.duplicates().join("; ")));
+        }
+
+        if self.use_inventory {
+            let conflicts = self.registry.merge_inventory();
+            if !conflicts.is_empty() {
+                return Err(ServerError::Config(conflicts.join("; ")));
+            }
+        }
+
+        let config = parse_sdl_sources(self.sdl, self.merge_types).map_err(|e| match e {
+            ParseError::SdlParseError(err) => ServerError::Parse(err),
+            ParseError::Conflict(errors) => ServerError::Conflict(errors),
+        })?;
 
         if self.validate_n1 {
             let detector = N1Detector::new(&config, &self.registry);
             detector.detect().map_err(ServerError::N1Detection)?;
         }
 
+        let mut warnings = Vec::new();
+        if self.validate_resolvers {
+            warnings = ResolverValidator::new(&config, &self.registry)
+                .validate()
+                .map_err(ServerError::Validation)?;
+            for warning in &warnings {
+                tracing::warn!("{}", warning);
+            }
+        }
+
         Ok(ValidatedServerBuilder {
             config,
             registry: self.registry,
+            shared_data: self.shared_data,
+            errors: self.errors,
             batch_delay: self.batch_delay,
             max_batch_size: self.max_batch_size,
+            warnings,
         })
     }
 
@@ -109,14 +266,24 @@ impl GraphQLServerBuilder {
 pub struct ValidatedServerBuilder {
     config: GraphQLConfig,
     registry: TraitRegistry,
+    shared_data: Data,
+    errors: ErrorMapper,
     batch_delay: Duration,
     max_batch_size: usize,
+    warnings: Vec<String>,
 }
 
 impl ValidatedServerBuilder {
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
     pub fn build(self) -> Result<GraphQLServer, ServerError> {
         let registry = Arc::new(self.registry);
-        let schema_builder = SchemaBuilder::new(self.config, registry.clone());
+        let schema_builder = SchemaBuilder::new(self.config, registry.clone())
+            .with_batching(self.batch_delay, self.max_batch_size)
+            .with_shared_data(Arc::new(self.shared_data))
+            .with_error_mapper(self.errors);
         let schema = schema_builder.build()?;
 
         Ok(GraphQLServer {
@@ -160,7 +327,49 @@ impl GraphQLServer {
         self.schema.execute(query).await
     }
 
+    pub async fn execute_request(
+        &self,
+        request: async_graphql::Request,
+    ) -> async_graphql::Response {
+        self.schema.execute(request).await
+    }
+
+    pub async fn execute_batch(
+        &self,
+        batch_request: async_graphql::BatchRequest,
+    ) -> async_graphql::BatchResponse {
+        self.schema.execute_batch(batch_request).await
+    }
+
     pub fn execute_sync(&self, query: &str) -> async_graphql::Response {
-        futures::executor::block_on(self.execute(query))
+        if tokio::runtime::Handle::try_current().is_err() {
+            return futures::executor::block_on(self.execute(query));
+        }
+
+        // Batch loads are spawned onto the current runtime, which this call
+        // would block, so the query runs on a thread with its own runtime.
+        std::thread::scope(|scope| {
+            scope
+                .spawn(|| {
+                    match tokio::runtime::Builder::new_current_thread()
+                        .enable_all()
+                        .build()
+                    {
+                        Ok(runtime) => runtime.block_on(self.execute(query)),
+                        Err(e) => async_graphql::Response::from_errors(vec![
+                            async_graphql::ServerError::new(
+                                format!("Failed to start a runtime for execute_sync: {}", e),
+                                None,
+                            ),
+                        ]),
+                    }
+                })
+                .join()
+                .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+        })
+    }
+
+    pub fn execute_stream(&self, query: &str) -> BoxStream<'static, async_graphql::Response> {
+        self.schema.execute_stream(query.to_string())
     }
 }
@@ -0,0 +1,45 @@
+use crate::config::SourcePosition;
+
+#[derive(Debug, Clone)]
+pub struct ValidationError {
+    pub type_name: String,
+    pub field_name: String,
+    pub message: String,
+    pub position: Option<SourcePosition>,
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Invalid field {}.{}: {}",
+            self.type_name, self.field_name, self.message
+        )?;
+        if let Some(position) = &self.position {
+            write!(f, "\n{}", position.snippet())?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validation_error_display() {
+        let error = ValidationError {
+            type_name: "Query".to_string(),
+            field_name: "user".to_string(),
+            message: "resolver 'getUsr' is not registered".to_string(),
+            position: None,
+        };
+
+        assert_eq!(
+            error.to_string(),
+            "Invalid field Query.user: resolver 'getUsr' is not registered"
+        );
+    }
+}
@@ -0,0 +1,5 @@
+mod error;
+mod validator;
+
+pub use error::ValidationError;
+pub(crate) use validator::ResolverValidator;
@@ -0,0 +1,288 @@
+use async_graphql_parser::Pos;
+use rustc_hash::FxHashSet;
+
+use crate::config::{ArgumentMapping, FieldConfig, GraphQLConfig, ResolverConfig, TypeConfig};
+use crate::registry::storage::TraitRegistry;
+use crate::validation::error::ValidationError;
+
+pub(crate) struct ResolverValidator<'a> {
+    config: &'a GraphQLConfig,
+    registry: &'a TraitRegistry,
+    errors: Vec<ValidationError>,
+    referenced: FxHashSet<&'a str>,
+}
+
+impl<'a> ResolverValidator<'a> {
+    pub fn new(config: &'a GraphQLConfig, registry: &'a TraitRegistry) -> Self {
+        Self {
+            config,
+            registry,
+            errors: Vec::new(),
+            referenced: FxHashSet::default(),
+        }
+    }
+
+    // Returns warnings for registered resolvers that no field references.
+    pub fn validate(mut self) -> Result<Vec<String>, Vec<ValidationError>> {
+        let mut types: Vec<_> = self.config.types.values().collect();
+        types.sort_by(|a, b| a.name.cmp(&b.name));
+
+        for type_config in types {
+            let is_subscription =
+                self.config.subscription_type.as_deref() == Some(type_config.name.as_str());
+            for field in &type_config.fields {
+                self.check_field(type_config, field, is_subscription);
+            }
+        }
+
+        if !self.errors.is_empty() {
+            return Err(self.errors);
+        }
+        Ok(self.unused_resolvers())
+    }
+
+    fn check_field(
+        &mut self,
+        type_config: &TypeConfig,
+        field: &'a FieldConfig,
+        is_subscription: bool,
+    ) {
+        let Some(ref resolver) = field.resolver else {
+            return;
+        };
+        let source = self.config.source.field(&type_config.name, &field.name);
+        let name = resolver.resolver_name();
+        self.referenced.insert(name);
+
+        let registry = self.registry;
+        let message = if is_subscription {
+            (!registry.has_subscription_resolver(name))
+                .then(|| format!("subscription resolver '{}' is not registered", name))
+        } else {
+            match resolver {
+                ResolverConfig::Trait {
+                    batch_key: None, ..
+                } => {
+                    if registry.has_resolver(name) {
+                        None
+                    } else if let Ok(batch_resolver) = registry.get_batch_resolver(name) {
+                        Some(format!(
+                            "'{}' is a batch resolver and needs @batchKey(field: \"{}\")",
+                            name,
+                            batch_resolver.batch_key_field()
+                        ))
+                    } else {
+                        Some(format!("resolver '{}' is not registered", name))
+                    }
+                }
+                ResolverConfig::Trait {
+                    batch_key: Some(_), ..
+                } => {
+                    if registry.has_batch_resolver(name) {
+                        None
+                    } else if registry.has_resolver(name) {
+                        Some(format!(
+                            "'{}' is not a batch resolver and cannot be used with @batchKey",
+                            name
+                        ))
+                    } else {
+                        Some(format!("batch resolver '{}' is not registered", name))
+                    }
+                }
+                ResolverConfig::Call { .. } => (!registry.has_resolver(name))
+                    .then(|| format!("@call target '{}' is not registered", name)),
+            }
+        };
+
+        if let Some(message) = message {
+            self.error(type_config, field, source.pos, message);
+        }
+
+        match resolver {
+            ResolverConfig::Trait {
+                batch_key: Some(key),
+                ..
+            } if !has_field(type_config, key) => {
+                let message = format!(
+                    "@batchKey field '{}' does not exist on type '{}'",
+                    key, type_config.name
+                );
+                self.error(
+                    type_config,
+                    field,
+                    source.batch_key.unwrap_or(source.pos),
+                    message,
+                );
+            }
+            ResolverConfig::Call { args, .. } => {
+                let pos = source.call.unwrap_or(source.pos);
+                let mut args: Vec<_> = args.iter().collect();
+                args.sort_by(|a, b| a.0.cmp(b.0));
+                for (arg_name, mapping) in args {
+                    let message = match mapping {
+                        ArgumentMapping::ParentField(parent_field)
+                            if !has_field(type_config, parent_field) =>
+                        {
+                            format!(
+                                "@call argument '{}' references '$parent.{}', but type '{}' has no field '{}'",
+                                arg_name, parent_field, type_config.name, parent_field
+                            )
+                        }
+                        ArgumentMapping::Argument(arg)
+                            if !field.arguments.iter().any(|a| &a.name == arg) =>
+                        {
+                            format!(
+                                "@call argument '{}' references '$arg.{}', but field '{}' has no argument '{}'",
+                                arg_name, arg, field.name, arg
+                            )
+                        }
+                        _ => continue,
+                    };
+                    self.error(type_config, field, pos, message);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn error(&mut self, type_config: &TypeConfig, field: &FieldConfig, pos: Pos, message: String) {
+        self.errors.push(ValidationError {
+            type_name: type_config.name.clone(),
+            field_name: field.name.clone(),
+            message,
+            position: self.config.source.locate(pos),
+        });
+    }
+
+    fn unused_resolvers(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        let kinds = [
+            (
+                "resolver",
+                self.registry.resolver_names().collect::<Vec<_>>(),
+            ),
+            (
+                "batch resolver",
+                self.registry.batch_resolver_names().collect(),
+            ),
+            (
+                "subscription resolver",
+                self.registry.subscription_resolver_names().collect(),
+            ),
+        ];
+        for (kind, mut names) in kinds {
+            names.sort();
+            for name in names {
+                if !self.referenced.contains(name.as_str()) {
+                    warnings.push(format!(
+                        "{} '{}' is registered but no field references it",
+                        kind, name
+                    ));
+                }
+            }
+        }
+        warnings
+    }
+}
+
+fn has_field(type_config: &TypeConfig, name: &str) -> bool {
+    type_config.fields.iter().any(|f| f.name == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::parse_sdl;
+    use crate::registry::resolver::{
+        BoxFuture, BoxStream, ResolverContext, ResolverResult, SubscriptionResolver,
+    };
+    use async_graphql::Value;
+    use rustc_hash::FxHashMap;
+
+    struct TestSubscription;
+
+    impl SubscriptionResolver for TestSubscription {
+        fn subscribe<'a>(
+            &'a self,
+            _ctx: &'a ResolverContext,
+            _args: FxHashMap<String, Value>,
+        ) -> BoxFuture<'a, ResolverResult<BoxStream<'static, ResolverResult<Value>>>> {
+            Box::pin(async { Ok(Box::pin(futures::stream::empty()) as _) })
+        }
+
+        fn name(&self) -> &'static str {
+            "ticks"
+        }
+    }
+
+    #[test]
+    fn test_validator_without_resolvers() {
+        let config = parse_sdl("type Query { hello: String }").unwrap();
+        let registry = TraitRegistry::default();
+
+        let warnings = ResolverValidator::new(&config, &registry)
+            .validate()
+            .unwrap();
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_validator_missing_resolver() {
+        let config = parse_sdl(r#"type Query { user: String @resolver(name: "getUsr") }"#).unwrap();
+        let registry = TraitRegistry::default();
+
+        let errors = ResolverValidator::new(&config, &registry)
+            .validate()
+            .unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].type_name, "Query");
+        assert_eq!(errors[0].field_name, "user");
+        assert_eq!(errors[0].message, "resolver 'getUsr' is not registered");
+    }
+
+    #[test]
+    fn test_validator_checks_interface_fields() {
+        let sdl = r#"
+            type Query { node: Node }
+            interface Node {
+                id: ID!
+                owner: String @resolver(name: "getOwner")
+                label: String @call(resolver: "getLabel", args: { id: "$parent.nodeId" })
+            }
+        "#;
+        let config = parse_sdl(sdl).unwrap();
+        let registry = TraitRegistry::default();
+
+        let errors = ResolverValidator::new(&config, &registry)
+            .validate()
+            .unwrap_err();
+        let messages: Vec<_> = errors
+            .iter()
+            .map(|e| format!("{}.{}: {}", e.type_name, e.field_name, e.message))
+            .collect();
+        assert_eq!(
+            messages,
+            [
+                "Node.owner: resolver 'getOwner' is not registered",
+                "Node.label: @call target 'getLabel' is not registered",
+                "Node.label: @call argument 'id' references '$parent.nodeId', but type 'Node' has no field 'nodeId'",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validator_subscription_resolvers() {
+        let sdl = r#"
+            type Query { hello: String }
+            type Subscription { ticks: Int @resolver(name: "ticks") }
+        "#;
+        let config = parse_sdl(sdl).unwrap();
+        let mut registry = TraitRegistry::default();
+        registry.register_subscription_resolver(TestSubscription);
+
+        let warnings = ResolverValidator::new(&config, &registry)
+            .validate()
+            .unwrap();
+        assert!(warnings.is_empty());
+    }
+}
@@ -170,7 +170,10 @@ fn test_n1_detection_passes_with_batch_key() {
         }
     "#;
 
-    let result = GraphQLServer::builder().sdl(sdl).build();
+    let result = GraphQLServer::builder()
+        .sdl(sdl)
+        .skip_resolver_validation()
+        .build();
     assert!(result.is_ok());
 }
 
@@ -195,6 +198,7 @@ fn test_skip_n1_validation() {
     let result = GraphQLServer::builder()
         .sdl(sdl)
         .skip_n1_validation()
+        .skip_resolver_validation()
         .build();
     assert!(result.is_ok());
 }
@@ -268,6 +272,7 @@ fn test_call_directive_parsing() {
         .sdl(sdl)
         .register_resolver(GetUserResolver)
         .skip_n1_validation()
+        .skip_resolver_validation()
         .build();
 
     assert!(result.is_ok());
@@ -334,6 +339,62 @@ fn test_execute_sync() {
     assert!(response.errors.is_empty());
 }
 
+#[tokio::test]
+async fn test_execute_sync_inside_runtime_with_batch_key() {
+    struct ListPostsResolver;
+
+    impl Resolver for ListPostsResolver {
+        fn name(&self) -> &'static str {
+            "listPosts"
+        }
+
+        fn resolve<'a>(
+            &'a self,
+            _ctx: &'a ResolverContext,
+            _args: FxHashMap<String, Value>,
+        ) -> BoxFuture<'a, ResolverResult<Value>> {
+            Box::pin(async move {
+                let posts = serde_json::json!([
+                    {"id": "p1", "authorId": "a1"},
+                    {"id": "p2", "authorId": "a2"}
+                ]);
+                Ok(serde_json::from_value(posts).unwrap())
+            })
+        }
+    }
+
+    let sdl = r#"
+        type Query {
+            posts: [Post!]! @resolver(name: "listPosts")
+        }
+
+        type Post {
+            id: ID!
+            authorId: ID!
+            author: User @resolver(name: "getUsersByIds") @batchKey(field: "authorId")
+        }
+
+        type User {
+            id: ID!
+            name: String!
+        }
+    "#;
+
+    let server = GraphQLServer::builder()
+        .sdl(sdl)
+        .register_resolver(ListPostsResolver)
+        .register_batch_resolver(GetUsersBatchResolver)
+        .build()
+        .unwrap();
+
+    let response = server.execute_sync("{ posts { id author { id } } }");
+    assert!(response.errors.is_empty(), "Errors: {:?}", response.errors);
+
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["posts"][0]["author"]["id"], "a1");
+    assert_eq!(data["posts"][1]["author"]["id"], "a2");
+}
+
 #[test]
 fn test_deeply_nested_n1_detection() {
     let sdl = r#"
@@ -605,112 +666,127 @@ async fn test_batch_resolver_returns_batched_data() {
 }
 
 #[tokio::test]
-async fn test_call_directive_maps_parent_field() {
-    struct GetProfileResolver;
+async fn test_batch_resolver_coalesces_sibling_keys() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
 
-    impl Resolver for GetProfileResolver {
+    struct CountingPostsResolver {
+        calls: Arc<AtomicUsize>,
+        batch_sizes: Arc<std::sync::Mutex<Vec<usize>>>,
+    }
+
+    impl ErasedBatchResolver for CountingPostsResolver {
         fn name(&self) -> &'static str {
-            "getProfile"
+            "getPostsByUser"
         }
 
-        fn resolve<'a>(
+        fn batch_key_field(&self) -> &'static str {
+            "id"
+        }
+
+        fn load_erased<'a>(
             &'a self,
             _ctx: &'a ResolverContext,
-            args: FxHashMap<String, Value>,
-        ) -> BoxFuture<'a, ResolverResult<Value>> {
+            keys: Vec<serde_json::Value>,
+        ) -> BoxFuture<'a, ResolverResult<Vec<(serde_json::Value, serde_json::Value)>>> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            self.batch_sizes.lock().unwrap().push(keys.len());
             Box::pin(async move {
-                let user_id = args
-                    .get("userId")
-                    .and_then(|v| match v {
-                        Value::String(s) => Some(s.clone()),
-                        _ => None,
+                Ok(keys
+                    .into_iter()
+                    .map(|user_id| {
+                        let posts = serde_json::json!([
+                            {"id": format!("{}-post-1", user_id), "title": "Post"}
+                        ]);
+                        (user_id, posts)
                     })
-                    .unwrap_or_default();
+                    .collect())
+            })
+        }
+    }
 
-                let profile = serde_json::json!({
-                    "bio": format!("Bio for user {}", user_id),
-                    "avatarUrl": format!("https://example.com/avatar/{}.png", user_id)
-                });
+    struct ListUsersResolver;
 
-                Ok(serde_json::from_value(profile).unwrap())
+    impl Resolver for ListUsersResolver {
+        fn name(&self) -> &'static str {
+            "listUsers"
+        }
+
+        fn resolve<'a>(
+            &'a self,
+            _ctx: &'a ResolverContext,
+            _args: FxHashMap<String, Value>,
+        ) -> BoxFuture<'a, ResolverResult<Value>> {
+            Box::pin(async move {
+                let users: Vec<_> = (1..=5)
+                    .map(|i| serde_json::json!({"id": format!("user-{}", i)}))
+                    .collect();
+                Ok(serde_json::from_value(serde_json::Value::Array(users)).unwrap())
             })
         }
     }
 
     let sdl = r#"
         type Query {
-            user(id: ID!): User @resolver(name: "getUser")
+            users: [User!]! @resolver(name: "listUsers")
         }
 
         type User {
             id: ID!
-            name: String!
-            profile: Profile @call(resolver: "getProfile", args: { userId: "$parent.id" })
+            posts: [Post!]! @resolver(name: "getPostsByUser") @batchKey(field: "id")
         }
 
-        type Profile {
-            bio: String!
-            avatarUrl: String!
+        type Post {
+            id: ID!
+            title: String!
         }
     "#;
 
+    let calls = Arc::new(AtomicUsize::new(0));
+    let batch_sizes = Arc::new(std::sync::Mutex::new(Vec::new()));
     let server = GraphQLServer::builder()
         .sdl(sdl)
-        .register_resolver(GetUserResolver)
-        .register_resolver(GetProfileResolver)
-        .skip_n1_validation()
+        .register_resolver(ListUsersResolver)
+        .register_batch_resolver(CountingPostsResolver {
+            calls: calls.clone(),
+            batch_sizes: batch_sizes.clone(),
+        })
+        .max_batch_size(3)
         .build()
         .unwrap();
 
-    let response = server
-        .execute(r#"{ user(id: "42") { id name profile { bio avatarUrl } } }"#)
-        .await;
-
+    let response = server.execute(r#"{ users { id posts { id } } }"#).await;
     assert!(response.errors.is_empty(), "Errors: {:?}", response.errors);
 
     let data = response.data.into_json().unwrap();
-    let user = &data["user"];
-
-    assert_eq!(user["id"], "42");
-    assert_eq!(user["name"], "User 42");
+    let users = data["users"].as_array().unwrap();
+    assert_eq!(users.len(), 5);
+    for user in users {
+        let id = user["id"].as_str().unwrap();
+        assert!(user["posts"][0]["id"].as_str().unwrap().contains(id));
+    }
 
-    let profile = &user["profile"];
-    assert_eq!(profile["bio"], "Bio for user 42");
-    assert!(profile["avatarUrl"].as_str().unwrap().contains("42"));
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    let mut sizes = batch_sizes.lock().unwrap().clone();
+    sizes.sort();
+    assert_eq!(sizes, vec![2, 3]);
 }
 
 #[tokio::test]
-async fn test_deeply_nested_resolver_data_flow() {
-    struct GetOrgsResolver;
-
-    impl Resolver for GetOrgsResolver {
-        fn name(&self) -> &'static str {
-            "getOrgs"
-        }
+async fn test_batch_resolver_deduplicates_and_caches_keys() {
+    use std::sync::{Arc, Mutex};
 
-        fn resolve<'a>(
-            &'a self,
-            _ctx: &'a ResolverContext,
-            _args: FxHashMap<String, Value>,
-        ) -> BoxFuture<'a, ResolverResult<Value>> {
-            Box::pin(async move {
-                let orgs = serde_json::json!([
-                    {"id": "org-1", "name": "Acme Corp"}
-                ]);
-                Ok(serde_json::from_value(orgs).unwrap())
-            })
-        }
+    struct RecordingAuthorsResolver {
+        batches: Arc<Mutex<Vec<Vec<serde_json::Value>>>>,
     }
 
-    struct GetTeamsByOrgResolver;
-
-    impl ErasedBatchResolver for GetTeamsByOrgResolver {
+    impl ErasedBatchResolver for RecordingAuthorsResolver {
         fn name(&self) -> &'static str {
-            "getTeamsByOrg"
+            "getUsersByIds"
         }
 
         fn batch_key_field(&self) -> &'static str {
-            "id"
+            "authorId"
         }
 
         fn load_erased<'a>(
@@ -718,152 +794,2786 @@ async fn test_deeply_nested_resolver_data_flow() {
             _ctx: &'a ResolverContext,
             keys: Vec<serde_json::Value>,
         ) -> BoxFuture<'a, ResolverResult<Vec<(serde_json::Value, serde_json::Value)>>> {
+            self.batches.lock().unwrap().push(keys.clone());
             Box::pin(async move {
-                let results: Vec<(serde_json::Value, serde_json::Value)> = keys
+                Ok(keys
                     .into_iter()
-                    .map(|org_id| {
-                        let teams = serde_json::json!([
-                            {"id": format!("{}-team-1", org_id), "name": "Engineering"},
-                            {"id": format!("{}-team-2", org_id), "name": "Design"}
-                        ]);
-                        (org_id, teams)
+                    .map(|key| {
+                        let user = serde_json::json!({"id": key, "name": format!("User {}", key)});
+                        (key, user)
                     })
-                    .collect();
-                Ok(results)
+                    .collect())
             })
         }
     }
 
-    struct GetMembersByTeamResolver;
+    struct ListPostsResolver;
 
-    impl ErasedBatchResolver for GetMembersByTeamResolver {
+    impl Resolver for ListPostsResolver {
         fn name(&self) -> &'static str {
-            "getMembersByTeam"
+            "listPosts"
         }
 
-        fn batch_key_field(&self) -> &'static str {
-            "id"
-        }
-
-        fn load_erased<'a>(
+        fn resolve<'a>(
             &'a self,
             _ctx: &'a ResolverContext,
-            keys: Vec<serde_json::Value>,
-        ) -> BoxFuture<'a, ResolverResult<Vec<(serde_json::Value, serde_json::Value)>>> {
+            _args: FxHashMap<String, Value>,
+        ) -> BoxFuture<'a, ResolverResult<Value>> {
             Box::pin(async move {
-                let results: Vec<(serde_json::Value, serde_json::Value)> = keys
-                    .into_iter()
-                    .map(|team_id| {
-                        let members = serde_json::json!([
-                            {"id": format!("{}-member-1", team_id), "name": "John"}
-                        ]);
-                        (team_id, members)
-                    })
-                    .collect();
-                Ok(results)
+                let posts = serde_json::json!([
+                    {"id": "p1", "authorId": "a1"},
+                    {"id": "p2", "authorId": "a1"},
+                    {"id": "p3", "authorId": "a2"},
+                    {"id": "p4", "authorId": "a1"}
+                ]);
+                Ok(serde_json::from_value(posts).unwrap())
             })
         }
     }
 
     let sdl = r#"
         type Query {
-            organizations: [Organization!]! @resolver(name: "getOrgs")
-        }
-
-        type Organization {
-            id: ID!
-            name: String!
-            teams: [Team!]! @resolver(name: "getTeamsByOrg") @batchKey(field: "id")
+            posts: [Post!]! @resolver(name: "listPosts")
         }
 
-        type Team {
+        type Post {
             id: ID!
-            name: String!
-            members: [Member!]! @resolver(name: "getMembersByTeam") @batchKey(field: "id")
+            authorId: ID!
+            author: User @resolver(name: "getUsersByIds") @batchKey(field: "authorId")
         }
 
-        type Member {
+        type User {
             id: ID!
             name: String!
         }
     "#;
 
+    let batches = Arc::new(Mutex::new(Vec::new()));
     let server = GraphQLServer::builder()
         .sdl(sdl)
-        .register_resolver(GetOrgsResolver)
-        .register_batch_resolver(GetTeamsByOrgResolver)
-        .register_batch_resolver(GetMembersByTeamResolver)
+        .register_resolver(ListPostsResolver)
+        .register_batch_resolver(RecordingAuthorsResolver {
+            batches: batches.clone(),
+        })
         .build()
         .unwrap();
 
     let response = server
-        .execute(
-            r#"{
-                organizations {
-                    id
-                    name
-                    teams {
-                        id
-                        name
-                        members {
-                            id
-                            name
-                        }
-                    }
-                }
-            }"#,
-        )
+        .execute(r#"{ posts { id author { id name } } }"#)
         .await;
-
     assert!(response.errors.is_empty(), "Errors: {:?}", response.errors);
 
     let data = response.data.into_json().unwrap();
-    let orgs = data["organizations"].as_array().unwrap();
+    let posts = data["posts"].as_array().unwrap();
+    assert_eq!(posts[0]["author"]["id"], "a1");
+    assert_eq!(posts[1]["author"]["id"], "a1");
+    assert_eq!(posts[2]["author"]["id"], "a2");
+    assert_eq!(posts[3]["author"]["id"], "a1");
 
-    assert_eq!(orgs.len(), 1);
-    assert_eq!(orgs[0]["id"], "org-1");
-    assert_eq!(orgs[0]["name"], "Acme Corp");
+    let recorded = batches.lock().unwrap().clone();
+    assert_eq!(recorded.len(), 1);
+    assert_eq!(recorded[0].len(), 2);
 
-    let teams = orgs[0]["teams"].as_array().unwrap();
-    assert_eq!(teams.len(), 2);
-    assert_eq!(teams[0]["name"], "Engineering");
-    assert_eq!(teams[1]["name"], "Design");
+    let response = server
+        .execute(r#"{ first: posts { author { id } } second: posts { author { name } } }"#)
+        .await;
+    assert!(response.errors.is_empty(), "Errors: {:?}", response.errors);
 
-    let members = teams[0]["members"].as_array().unwrap();
-    assert_eq!(members.len(), 1);
-    assert_eq!(members[0]["name"], "John");
-    assert!(members[0]["id"].as_str().unwrap().contains("team-1"));
+    let recorded = batches.lock().unwrap().clone();
+    let keys_loaded: usize = recorded[1..].iter().map(|batch| batch.len()).sum();
+    assert_eq!(keys_loaded, 2);
 }
 
-#[test]
-fn test_multiple_sdl_with_shared_types() {
-    let common_sdl = r#"
-        type User {
-            id: ID!
-            name: String!
-        }
-    "#;
+#[tokio::test]
+async fn test_typed_batch_resolver() {
+    use graphql_resolver::BatchResolver;
 
-    let posts_sdl = r#"
-        type Post {
-            id: ID!
-            title: String!
-            authorId: ID!
-        }
-    "#;
+    #[derive(Clone, serde::Serialize)]
+    struct Author {
+        id: String,
+        name: String,
+    }
 
-    let query_sdl = r#"
-        type Query {
-            user(id: ID!): User @resolver(name: "getUser")
-        }
-    "#;
+    struct AuthorsByIdResolver;
 
-    let result = GraphQLServer::builder()
-        .sdl(common_sdl)
-        .sdl(posts_sdl)
-        .sdl(query_sdl)
+    impl BatchResolver for AuthorsByIdResolver {
+        type Key = String;
+        type Value = Author;
+
+        fn load<'a>(
+            &'a self,
+            _ctx: &'a ResolverContext,
+            keys: Vec<Self::Key>,
+        ) -> BoxFuture<'a, ResolverResult<Vec<(Self::Key, Self::Value)>>> {
+            Box::pin(async move {
+                Ok(keys
+                    .into_iter()
+                    .map(|id| {
+                        let author = Author {
+                            id: id.clone(),
+                            name: format!("Author {}", id),
+                        };
+                        (id, author)
+                    })
+                    .collect())
+            })
+        }
+
+        fn name(&self) -> &'static str {
+            "getAuthorsByIds"
+        }
+
+        fn batch_key_field(&self) -> &'static str {
+            "authorId"
+        }
+    }
+
+    struct ListPostsResolver;
+
+    impl Resolver for ListPostsResolver {
+        fn name(&self) -> &'static str {
+            "listPosts"
+        }
+
+        fn resolve<'a>(
+            &'a self,
+            _ctx: &'a ResolverContext,
+            _args: FxHashMap<String, Value>,
+        ) -> BoxFuture<'a, ResolverResult<Value>> {
+            Box::pin(async move {
+                let posts = serde_json::json!([
+                    {"id": "p1", "authorId": "a1", "legacyAuthorId": 7},
+                    {"id": "p2", "authorId": "a2", "legacyAuthorId": 8}
+                ]);
+                Ok(serde_json::from_value(posts).unwrap())
+            })
+        }
+    }
+
+    let sdl = r#"
+        type Query {
+            posts: [Post!]! @resolver(name: "listPosts")
+        }
+
+        type Post {
+            id: ID!
+            authorId: ID!
+            legacyAuthorId: Int!
+            author: Author @resolver(name: "getAuthorsByIds") @batchKey(field: "authorId")
+            legacyAuthor: Author @resolver(name: "getAuthorsByIds") @batchKey(field: "legacyAuthorId")
+        }
+
+        type Author {
+            id: ID!
+            name: String!
+        }
+    "#;
+
+    let server = GraphQLServer::builder()
+        .sdl(sdl)
+        .register_resolver(ListPostsResolver)
+        .register_batch_resolver(AuthorsByIdResolver)
+        .build()
+        .unwrap();
+
+    let response = server
+        .execute(r#"{ posts { id author { id name } } }"#)
+        .await;
+    assert!(response.errors.is_empty(), "Errors: {:?}", response.errors);
+
+    let data = response.data.into_json().unwrap();
+    let posts = data["posts"].as_array().unwrap();
+    assert_eq!(posts[0]["author"]["id"], "a1");
+    assert_eq!(posts[0]["author"]["name"], "Author a1");
+    assert_eq!(posts[1]["author"]["name"], "Author a2");
+
+    let response = server.execute(r#"{ posts { legacyAuthor { id } } }"#).await;
+    assert!(!response.errors.is_empty());
+    assert!(response.errors[0].message.contains("Invalid batch key"));
+    assert!(response.errors[0].message.contains("getAuthorsByIds"));
+}
+
+#[tokio::test]
+async fn test_cached_batch_key_is_reloaded_for_a_wider_selection() {
+    struct ProjectingAuthorsResolver;
+
+    impl ErasedBatchResolver for ProjectingAuthorsResolver {
+        fn name(&self) -> &'static str {
+            "getAuthorsByIds"
+        }
+
+        fn batch_key_field(&self) -> &'static str {
+            "authorId"
+        }
+
+        fn load_erased<'a>(
+            &'a self,
+            ctx: &'a ResolverContext,
+            keys: Vec<serde_json::Value>,
+        ) -> BoxFuture<'a, ResolverResult<Vec<(serde_json::Value, serde_json::Value)>>> {
+            Box::pin(async move {
+                Ok(keys
+                    .into_iter()
+                    .map(|key| {
+                        let mut author = serde_json::Map::new();
+                        for field in ctx.selection() {
+                            let value = match field.name() {
+                                "id" => key.clone(),
+                                "name" => format!("Author {}", key.as_str().unwrap()).into(),
+                                _ => continue,
+                            };
+                            author.insert(field.name().to_string(), value);
+                        }
+                        (key, author.into())
+                    })
+                    .collect())
+            })
+        }
+    }
+
+    struct ListPostsResolver;
+
+    impl Resolver for ListPostsResolver {
+        fn name(&self) -> &'static str {
+            "listPosts"
+        }
+
+        fn resolve<'a>(
+            &'a self,
+            _ctx: &'a ResolverContext,
+            _args: FxHashMap<String, Value>,
+        ) -> BoxFuture<'a, ResolverResult<Value>> {
+            Box::pin(async move {
+                Ok(
+                    serde_json::from_value(serde_json::json!([{"id": "p1", "authorId": "a1"}]))
+                        .unwrap(),
+                )
+            })
+        }
+    }
+
+    struct ListUsersResolver;
+
+    impl Resolver for ListUsersResolver {
+        fn name(&self) -> &'static str {
+            "listUsers"
+        }
+
+        fn resolve<'a>(
+            &'a self,
+            _ctx: &'a ResolverContext,
+            _args: FxHashMap<String, Value>,
+        ) -> BoxFuture<'a, ResolverResult<Value>> {
+            Box::pin(async move {
+                // Resolve after the first batch of authors has been cached.
+                tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+                Ok(serde_json::from_value(serde_json::json!([
+                    {"id": "u1", "posts": [{"id": "p1", "authorId": "a1"}]}
+                ]))
+                .unwrap())
+            })
+        }
+    }
+
+    let sdl = r#"
+        type Query {
+            posts: [Post!]! @resolver(name: "listPosts")
+            users: [User!]! @resolver(name: "listUsers")
+        }
+
+        type User {
+            id: ID!
+            posts: [Post!]!
+        }
+
+        type Post {
+            id: ID!
+            authorId: ID!
+            author: Author @resolver(name: "getAuthorsByIds") @batchKey(field: "authorId")
+        }
+
+        type Author {
+            id: ID!
+            name: String
+        }
+    "#;
+
+    let server = GraphQLServer::builder()
+        .sdl(sdl)
+        .register_resolver(ListPostsResolver)
+        .register_resolver(ListUsersResolver)
+        .register_batch_resolver(ProjectingAuthorsResolver)
+        .build()
+        .unwrap();
+
+    let response = server
+        .execute("{ posts { author { id } } users { posts { author { name } } } }")
+        .await;
+    assert!(response.errors.is_empty(), "Errors: {:?}", response.errors);
+
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["posts"][0]["author"]["id"], "a1");
+    assert_eq!(data["users"][0]["posts"][0]["author"]["name"], "Author a1");
+}
+
+#[tokio::test]
+async fn test_null_batch_key_resolves_to_null() {
+    use graphql_resolver::BatchResolver;
+    use std::sync::{Arc, Mutex};
+
+    struct AuthorsResolver {
+        keys: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl BatchResolver for AuthorsResolver {
+        type Key = String;
+        type Value = serde_json::Value;
+
+        fn load<'a>(
+            &'a self,
+            _ctx: &'a ResolverContext,
+            keys: Vec<String>,
+        ) -> BoxFuture<'a, ResolverResult<Vec<(String, serde_json::Value)>>> {
+            self.keys.lock().unwrap().extend(keys.iter().cloned());
+            Box::pin(async move {
+                Ok(keys
+                    .into_iter()
+                    .map(|id| (id.clone(), serde_json::json!({ "id": id })))
+                    .collect())
+            })
+        }
+
+        fn name(&self) -> &'static str {
+            "getAuthorsByIds"
+        }
+
+        fn batch_key_field(&self) -> &'static str {
+            "authorId"
+        }
+    }
+
+    struct ListPostsResolver;
+
+    impl Resolver for ListPostsResolver {
+        fn name(&self) -> &'static str {
+            "listPosts"
+        }
+
+        fn resolve<'a>(
+            &'a self,
+            _ctx: &'a ResolverContext,
+            _args: FxHashMap<String, Value>,
+        ) -> BoxFuture<'a, ResolverResult<Value>> {
+            Box::pin(async move {
+                Ok(serde_json::from_value(serde_json::json!([
+                    {"id": "p1", "authorId": "a1"},
+                    {"id": "p2", "authorId": null},
+                    {"id": "p3"},
+                ]))
+                .unwrap())
+            })
+        }
+    }
+
+    let sdl = r#"
+        type Query {
+            posts: [Post!]! @resolver(name: "listPosts")
+        }
+
+        type Post {
+            id: ID!
+            authorId: ID
+            author: Author @resolver(name: "getAuthorsByIds") @batchKey(field: "authorId")
+        }
+
+        type Author {
+            id: ID!
+        }
+    "#;
+
+    let keys = Arc::new(Mutex::new(Vec::new()));
+    let server = GraphQLServer::builder()
+        .sdl(sdl)
+        .register_resolver(ListPostsResolver)
+        .register_batch_resolver(AuthorsResolver { keys: keys.clone() })
+        .build()
+        .unwrap();
+
+    let response = server.execute("{ posts { id author { id } } }").await;
+    assert!(response.errors.is_empty(), "Errors: {:?}", response.errors);
+
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["posts"][0]["author"]["id"], "a1");
+    assert!(data["posts"][1]["author"].is_null());
+    assert!(data["posts"][2]["author"].is_null());
+    assert_eq!(*keys.lock().unwrap(), ["a1"]);
+}
+
+#[tokio::test]
+async fn test_call_directive_maps_parent_field() {
+    struct GetProfileResolver;
+
+    impl Resolver for GetProfileResolver {
+        fn name(&self) -> &'static str {
+            "getProfile"
+        }
+
+        fn resolve<'a>(
+            &'a self,
+            _ctx: &'a ResolverContext,
+            args: FxHashMap<String, Value>,
+        ) -> BoxFuture<'a, ResolverResult<Value>> {
+            Box::pin(async move {
+                let user_id = args
+                    .get("userId")
+                    .and_then(|v| match v {
+                        Value::String(s) => Some(s.clone()),
+                        _ => None,
+                    })
+                    .unwrap_or_default();
+
+                let profile = serde_json::json!({
+                    "bio": format!("Bio for user {}", user_id),
+                    "avatarUrl": format!("https://example.com/avatar/{}.png", user_id)
+                });
+
+                Ok(serde_json::from_value(profile).unwrap())
+            })
+        }
+    }
+
+    let sdl = r#"
+        type Query {
+            user(id: ID!): User @resolver(name: "getUser")
+        }
+
+        type User {
+            id: ID!
+            name: String!
+            profile: Profile @call(resolver: "getProfile", args: { userId: "$parent.id" })
+        }
+
+        type Profile {
+            bio: String!
+            avatarUrl: String!
+        }
+    "#;
+
+    let server = GraphQLServer::builder()
+        .sdl(sdl)
         .register_resolver(GetUserResolver)
+        .register_resolver(GetProfileResolver)
+        .skip_n1_validation()
+        .build()
+        .unwrap();
+
+    let response = server
+        .execute(r#"{ user(id: "42") { id name profile { bio avatarUrl } } }"#)
+        .await;
+
+    assert!(response.errors.is_empty(), "Errors: {:?}", response.errors);
+
+    let data = response.data.into_json().unwrap();
+    let user = &data["user"];
+
+    assert_eq!(user["id"], "42");
+    assert_eq!(user["name"], "User 42");
+
+    let profile = &user["profile"];
+    assert_eq!(profile["bio"], "Bio for user 42");
+    assert!(profile["avatarUrl"].as_str().unwrap().contains("42"));
+}
+
+#[tokio::test]
+async fn test_deeply_nested_resolver_data_flow() {
+    struct GetOrgsResolver;
+
+    impl Resolver for GetOrgsResolver {
+        fn name(&self) -> &'static str {
+            "getOrgs"
+        }
+
+        fn resolve<'a>(
+            &'a self,
+            _ctx: &'a ResolverContext,
+            _args: FxHashMap<String, Value>,
+        ) -> BoxFuture<'a, ResolverResult<Value>> {
+            Box::pin(async move {
+                let orgs = serde_json::json!([
+                    {"id": "org-1", "name": "Acme Corp"}
+                ]);
+                Ok(serde_json::from_value(orgs).unwrap())
+            })
+        }
+    }
+
+    struct GetTeamsByOrgResolver;
+
+    impl ErasedBatchResolver for GetTeamsByOrgResolver {
+        fn name(&self) -> &'static str {
+            "getTeamsByOrg"
+        }
+
+        fn batch_key_field(&self) -> &'static str {
+            "id"
+        }
+
+        fn load_erased<'a>(
+            &'a self,
+            _ctx: &'a ResolverContext,
+            keys: Vec<serde_json::Value>,
+        ) -> BoxFuture<'a, ResolverResult<Vec<(serde_json::Value, serde_json::Value)>>> {
+            Box::pin(async move {
+                let results: Vec<(serde_json::Value, serde_json::Value)> = keys
+                    .into_iter()
+                    .map(|org_id| {
+                        let teams = serde_json::json!([
+                            {"id": format!("{}-team-1", org_id), "name": "Engineering"},
+                            {"id": format!("{}-team-2", org_id), "name": "Design"}
+                        ]);
+                        (org_id, teams)
+                    })
+                    .collect();
+                Ok(results)
+            })
+        }
+    }
+
+    struct GetMembersByTeamResolver;
+
+    impl ErasedBatchResolver for GetMembersByTeamResolver {
+        fn name(&self) -> &'static str {
+            "getMembersByTeam"
+        }
+
+        fn batch_key_field(&self) -> &'static str {
+            "id"
+        }
+
+        fn load_erased<'a>(
+            &'a self,
+            _ctx: &'a ResolverContext,
+            keys: Vec<serde_json::Value>,
+        ) -> BoxFuture<'a, ResolverResult<Vec<(serde_json::Value, serde_json::Value)>>> {
+            Box::pin(async move {
+                let results: Vec<(serde_json::Value, serde_json::Value)> = keys
+                    .into_iter()
+                    .map(|team_id| {
+                        let members = serde_json::json!([
+                            {"id": format!("{}-member-1", team_id), "name": "John"}
+                        ]);
+                        (team_id, members)
+                    })
+                    .collect();
+                Ok(results)
+            })
+        }
+    }
+
+    let sdl = r#"
+        type Query {
+            organizations: [Organization!]! @resolver(name: "getOrgs")
+        }
+
+        type Organization {
+            id: ID!
+            name: String!
+            teams: [Team!]! @resolver(name: "getTeamsByOrg") @batchKey(field: "id")
+        }
+
+        type Team {
+            id: ID!
+            name: String!
+            members: [Member!]! @resolver(name: "getMembersByTeam") @batchKey(field: "id")
+        }
+
+        type Member {
+            id: ID!
+            name: String!
+        }
+    "#;
+
+    let server = GraphQLServer::builder()
+        .sdl(sdl)
+        .register_resolver(GetOrgsResolver)
+        .register_batch_resolver(GetTeamsByOrgResolver)
+        .register_batch_resolver(GetMembersByTeamResolver)
+        .build()
+        .unwrap();
+
+    let response = server
+        .execute(
+            r#"{
+                organizations {
+                    id
+                    name
+                    teams {
+                        id
+                        name
+                        members {
+                            id
+                            name
+                        }
+                    }
+                }
+            }"#,
+        )
+        .await;
+
+    assert!(response.errors.is_empty(), "Errors: {:?}", response.errors);
+
+    let data = response.data.into_json().unwrap();
+    let orgs = data["organizations"].as_array().unwrap();
+
+    assert_eq!(orgs.len(), 1);
+    assert_eq!(orgs[0]["id"], "org-1");
+    assert_eq!(orgs[0]["name"], "Acme Corp");
+
+    let teams = orgs[0]["teams"].as_array().unwrap();
+    assert_eq!(teams.len(), 2);
+    assert_eq!(teams[0]["name"], "Engineering");
+    assert_eq!(teams[1]["name"], "Design");
+
+    let members = teams[0]["members"].as_array().unwrap();
+    assert_eq!(members.len(), 1);
+    assert_eq!(members[0]["name"], "John");
+    assert!(members[0]["id"].as_str().unwrap().contains("team-1"));
+}
+
+#[test]
+fn test_multiple_sdl_with_shared_types() {
+    let common_sdl = r#"
+        type User {
+            id: ID!
+            name: String!
+        }
+    "#;
+
+    let posts_sdl = r#"
+        type Post {
+            id: ID!
+            title: String!
+            authorId: ID!
+        }
+    "#;
+
+    let query_sdl = r#"
+        type Query {
+            user(id: ID!): User @resolver(name: "getUser")
+        }
+    "#;
+
+    let result = GraphQLServer::builder()
+        .sdl(common_sdl)
+        .sdl(posts_sdl)
+        .sdl(query_sdl)
+        .register_resolver(GetUserResolver)
+        .build();
+
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_mutation_fields_execute_serially() {
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    struct SetHelloResolver {
+        events: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Resolver for SetHelloResolver {
+        fn name(&self) -> &'static str {
+            "setHello"
+        }
+
+        fn resolve<'a>(
+            &'a self,
+            _ctx: &'a ResolverContext,
+            args: FxHashMap<String, Value>,
+        ) -> BoxFuture<'a, ResolverResult<Value>> {
+            Box::pin(async move {
+                let msg = match args.get("msg") {
+                    Some(Value::String(s)) => s.clone(),
+                    _ => String::new(),
+                };
+                self.events.lock().unwrap().push(format!("start {}", msg));
+                let delay = if msg == "first" { 20 } else { 1 };
+                tokio::time::sleep(Duration::from_millis(delay)).await;
+                self.events.lock().unwrap().push(format!("end {}", msg));
+                Ok(Value::String(msg))
+            })
+        }
+    }
+
+    let sdl = r#"
+        type Query {
+            hello: String
+        }
+
+        type Mutation {
+            setHello(msg: String!): String @resolver(name: "setHello")
+        }
+    "#;
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let server = GraphQLServer::builder()
+        .sdl(sdl)
+        .register_resolver(SetHelloResolver {
+            events: events.clone(),
+        })
+        .build()
+        .unwrap();
+
+    let response = server
+        .execute(r#"mutation { a: setHello(msg: "first") b: setHello(msg: "second") }"#)
+        .await;
+
+    assert!(response.errors.is_empty(), "Errors: {:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["a"], "first");
+    assert_eq!(data["b"], "second");
+    assert_eq!(
+        *events.lock().unwrap(),
+        vec!["start first", "end first", "start second", "end second"]
+    );
+}
+
+#[tokio::test]
+async fn test_mutation_payload_batch_fields() {
+    struct CreatePostsResolver;
+
+    impl Resolver for CreatePostsResolver {
+        fn name(&self) -> &'static str {
+            "createPosts"
+        }
+
+        fn resolve<'a>(
+            &'a self,
+            _ctx: &'a ResolverContext,
+            _args: FxHashMap<String, Value>,
+        ) -> BoxFuture<'a, ResolverResult<Value>> {
+            Box::pin(async move {
+                let posts = serde_json::json!([
+                    {"id": "p1", "authorId": "a1"},
+                    {"id": "p2", "authorId": "a2"}
+                ]);
+                Ok(serde_json::from_value(posts).unwrap())
+            })
+        }
+    }
+
+    let sdl = r#"
+        type Query {
+            hello: String
+        }
+
+        type Mutation {
+            createPosts: [Post!]! @resolver(name: "createPosts")
+        }
+
+        type Post {
+            id: ID!
+            authorId: ID!
+            author: User @resolver(name: "getUsersByIds") @batchKey(field: "authorId")
+        }
+
+        type User {
+            id: ID!
+            name: String!
+        }
+    "#;
+
+    let server = GraphQLServer::builder()
+        .sdl(sdl)
+        .register_resolver(CreatePostsResolver)
+        .register_batch_resolver(GetUsersBatchResolver)
+        .build()
+        .unwrap();
+
+    let response = server
+        .execute(r#"mutation { createPosts { id author { id } } }"#)
+        .await;
+
+    assert!(response.errors.is_empty(), "Errors: {:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    let posts = data["createPosts"].as_array().unwrap();
+    assert_eq!(posts[0]["author"]["id"], "a1");
+    assert_eq!(posts[1]["author"]["id"], "a2");
+}
+
+#[test]
+fn test_n1_detection_in_mutation_payload() {
+    let sdl = r#"
+        type Query {
+            hello: String
+        }
+
+        type Mutation {
+            importUsers: [User!]! @resolver(name: "importUsers")
+        }
+
+        type User {
+            id: ID!
+            posts: [Post!]! @resolver(name: "getPostsByUser")
+        }
+
+        type Post {
+            id: ID!
+        }
+    "#;
+
+    let result = GraphQLServer::builder().sdl(sdl).build();
+    match result.err().unwrap() {
+        ServerError::N1Detection(errors) => {
+            assert_eq!(errors.len(), 1);
+            assert_eq!(errors[0].path, vec!["Mutation", "importUsers", "posts"]);
+        }
+        _ => panic!("Expected N1Detection error"),
+    }
+}
+
+struct CountdownSubscription;
+
+impl graphql_resolver::SubscriptionResolver for CountdownSubscription {
+    fn subscribe<'a>(
+        &'a self,
+        _ctx: &'a ResolverContext,
+        args: FxHashMap<String, Value>,
+    ) -> BoxFuture<'a, ResolverResult<graphql_resolver::BoxStream<'static, ResolverResult<Value>>>>
+    {
+        Box::pin(async move {
+            let from = match args.get("from") {
+                Some(Value::Number(n)) => n.as_i64().unwrap_or(0),
+                _ => return Err(ResolverError::Argument("from is required".to_string())),
+            };
+            let events = (1..=from).rev().map(|i| {
+                Ok(serde_json::from_value(
+                    serde_json::json!({ "remaining": i, "authorId": format!("a{}", i) }),
+                )
+                .unwrap())
+            });
+            let stream: graphql_resolver::BoxStream<'static, ResolverResult<Value>> =
+                Box::pin(futures::stream::iter(events.collect::<Vec<_>>()));
+            Ok(stream)
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "countdown"
+    }
+}
+
+#[tokio::test]
+async fn test_subscription_streams_values() {
+    use futures::StreamExt;
+
+    let sdl = r#"
+        type Query {
+            hello: String
+        }
+
+        type Subscription {
+            countdown(from: Int!): Tick! @resolver(name: "countdown")
+        }
+
+        type Tick {
+            remaining: Int!
+            authorId: ID!
+            author: User @resolver(name: "getUsersByIds") @batchKey(field: "authorId")
+        }
+
+        type User {
+            id: ID!
+            name: String!
+        }
+    "#;
+
+    let server = GraphQLServer::builder()
+        .sdl(sdl)
+        .register_subscription_resolver(CountdownSubscription)
+        .register_batch_resolver(GetUsersBatchResolver)
+        .build()
+        .unwrap();
+
+    let responses: Vec<_> = server
+        .execute_stream("subscription { countdown(from: 3) { remaining author { id } } }")
+        .collect()
+        .await;
+
+    assert_eq!(responses.len(), 3);
+    for (response, expected) in responses.into_iter().zip([3, 2, 1]) {
+        assert!(response.errors.is_empty(), "Errors: {:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        assert_eq!(data["countdown"]["remaining"], expected);
+        assert_eq!(data["countdown"]["author"]["id"], format!("a{}", expected));
+    }
+}
+
+#[tokio::test]
+async fn test_execute_stream_runs_queries_once() {
+    use futures::StreamExt;
+
+    let sdl = r#"
+        type Query {
+            user(id: ID!): User @resolver(name: "getUser")
+        }
+
+        type Subscription {
+            countdown(from: Int!): Int @resolver(name: "countdown")
+        }
+
+        type User {
+            id: ID!
+            name: String!
+        }
+    "#;
+
+    let server = GraphQLServer::builder()
+        .sdl(sdl)
+        .register_resolver(GetUserResolver)
+        .register_subscription_resolver(CountdownSubscription)
+        .build()
+        .unwrap();
+
+    let responses: Vec<_> = server
+        .execute_stream(r#"{ user(id: "7") { name } }"#)
+        .collect()
+        .await;
+
+    assert_eq!(responses.len(), 1);
+    let data = responses[0].data.clone().into_json().unwrap();
+    assert_eq!(data["user"]["name"], "User 7");
+}
+
+#[tokio::test]
+async fn test_subscription_missing_resolver() {
+    use futures::StreamExt;
+
+    let sdl = r#"
+        type Query {
+            hello: String
+        }
+
+        type Subscription {
+            ticks: Int @resolver(name: "ticks")
+        }
+    "#;
+
+    match GraphQLServer::builder().sdl(sdl).build() {
+        Err(ServerError::Validation(errors)) => {
+            assert_eq!(errors.len(), 1);
+            assert_eq!(
+                errors[0].message,
+                "subscription resolver 'ticks' is not registered"
+            );
+        }
+        _ => panic!("Expected Validation error"),
+    }
+
+    let server = GraphQLServer::builder()
+        .sdl(sdl)
+        .skip_resolver_validation()
+        .build()
+        .unwrap();
+
+    let responses: Vec<_> = server
+        .execute_stream("subscription { ticks }")
+        .collect()
+        .await;
+
+    assert_eq!(responses.len(), 1);
+    assert!(responses[0].errors[0]
+        .message
+        .contains("Resolver not found: ticks"));
+}
+
+#[test]
+fn test_subscription_field_without_resolver_fails_build() {
+    let sdl = r#"
+        type Query {
+            hello: String
+        }
+
+        type Subscription {
+            ticks: Int
+        }
+    "#;
+
+    let result = GraphQLServer::builder().sdl(sdl).build();
+    match result.err().unwrap() {
+        ServerError::Resolver(ResolverError::Execution(msg)) => {
+            assert!(msg.contains("Subscription.ticks"))
+        }
+        _ => panic!("Expected Resolver error"),
+    }
+}
+
+#[tokio::test]
+async fn test_input_objects_and_enums() {
+    struct CreateUserResolver;
+
+    impl Resolver for CreateUserResolver {
+        fn name(&self) -> &'static str {
+            "createUser"
+        }
+
+        fn resolve<'a>(
+            &'a self,
+            _ctx: &'a ResolverContext,
+            args: FxHashMap<String, Value>,
+        ) -> BoxFuture<'a, ResolverResult<Value>> {
+            Box::pin(async move {
+                let Some(Value::Object(input)) = args.get("input") else {
+                    return Err(ResolverError::Argument("input is required".to_string()));
+                };
+                let mut user = input.clone();
+                user.insert(
+                    async_graphql::Name::new("id"),
+                    Value::String("u1".to_string()),
+                );
+                Ok(Value::Object(user))
+            })
+        }
+    }
+
+    let sdl = r#"
+        type Query {
+            hello: String
+        }
+
+        type Mutation {
+            createUser(input: CreateUserInput!): User! @resolver(name: "createUser")
+        }
+
+        input CreateUserInput {
+            name: String!
+            role: Role = MEMBER
+            tags: [String!] = ["new"]
+        }
+
+        enum Role {
+            ADMIN
+            MEMBER
+        }
+
+        type User {
+            id: ID!
+            name: String!
+            role: Role!
+            tags: [String!]
+        }
+    "#;
+
+    let server = GraphQLServer::builder()
+        .sdl(sdl)
+        .register_resolver(CreateUserResolver)
+        .build()
+        .unwrap();
+
+    let response = server
+        .execute(r#"mutation { createUser(input: { name: "Ada" }) { id name role tags } }"#)
+        .await;
+    assert!(response.errors.is_empty(), "Errors: {:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["createUser"]["name"], "Ada");
+    assert_eq!(data["createUser"]["role"], "MEMBER");
+    assert_eq!(data["createUser"]["tags"], serde_json::json!(["new"]));
+
+    let response = server
+        .execute(r#"mutation { createUser(input: { name: "Root", role: ADMIN }) { role } }"#)
+        .await;
+    assert!(response.errors.is_empty(), "Errors: {:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["createUser"]["role"], "ADMIN");
+
+    let response = server
+        .execute(r#"mutation { createUser(input: { name: "X", role: OWNER }) { role } }"#)
+        .await;
+    assert!(!response.errors.is_empty());
+}
+
+#[tokio::test]
+async fn test_interfaces_and_unions_resolve_concrete_types() {
+    struct SearchResolver;
+
+    impl Resolver for SearchResolver {
+        fn name(&self) -> &'static str {
+            "search"
+        }
+
+        fn resolve<'a>(
+            &'a self,
+            _ctx: &'a ResolverContext,
+            _args: FxHashMap<String, Value>,
+        ) -> BoxFuture<'a, ResolverResult<Value>> {
+            Box::pin(async move {
+                Ok(Value::from_json(serde_json::json!([
+                    {"__typename": "User", "id": "u1", "name": "Alice"},
+                    {"__typename": "Post", "id": "p1", "title": "Hello"}
+                ]))
+                .unwrap())
+            })
+        }
+    }
+
+    struct NodeResolver;
+
+    impl Resolver for NodeResolver {
+        fn name(&self) -> &'static str {
+            "node"
+        }
+
+        fn resolve<'a>(
+            &'a self,
+            _ctx: &'a ResolverContext,
+            args: FxHashMap<String, Value>,
+        ) -> BoxFuture<'a, ResolverResult<Value>> {
+            Box::pin(async move {
+                let json = match args.get("id") {
+                    Some(Value::String(id)) if id.starts_with('u') => {
+                        serde_json::json!({"kind": "User", "id": id, "name": "Alice"})
+                    }
+                    Some(Value::String(id)) if id.starts_with('p') => {
+                        serde_json::json!({"kind": "Post", "id": id, "title": "Hello"})
+                    }
+                    _ => serde_json::json!({"id": "x"}),
+                };
+                Ok(Value::from_json(json).unwrap())
+            })
+        }
+    }
+
+    let sdl = r#"
+        type Query {
+            search: [SearchResult!]! @resolver(name: "search")
+            node(id: ID!): Node @resolver(name: "node")
+        }
+
+        interface Node @typeDiscriminator(field: "kind") {
+            id: ID!
+        }
+
+        union SearchResult = User | Post
+
+        type User implements Node {
+            id: ID!
+            name: String!
+        }
+
+        type Post implements Node {
+            id: ID!
+            title: String!
+        }
+    "#;
+
+    let server = GraphQLServer::builder()
+        .sdl(sdl)
+        .register_resolver(SearchResolver)
+        .register_resolver(NodeResolver)
+        .build()
+        .unwrap();
+
+    let response = server
+        .execute(
+            r#"{
+                search {
+                    __typename
+                    ... on User { name }
+                    ... on Post { title }
+                }
+            }"#,
+        )
+        .await;
+    assert!(response.errors.is_empty(), "Errors: {:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(
+        data["search"],
+        serde_json::json!([
+            {"__typename": "User", "name": "Alice"},
+            {"__typename": "Post", "title": "Hello"}
+        ])
+    );
+
+    let response = server
+        .execute(r#"{ node(id: "p1") { id __typename ... on Post { title } } }"#)
+        .await;
+    assert!(response.errors.is_empty(), "Errors: {:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(
+        data["node"],
+        serde_json::json!({"id": "p1", "__typename": "Post", "title": "Hello"})
+    );
+
+    let response = server.execute(r#"{ node(id: "x") { id } }"#).await;
+    assert_eq!(response.errors.len(), 1);
+    assert!(response.errors[0].message.contains("'kind'"));
+}
+
+struct EmailCodec;
+
+impl graphql_resolver::ScalarCodec for EmailCodec {
+    fn name(&self) -> &'static str {
+        "Email"
+    }
+
+    fn parse(&self, value: Value) -> ResolverResult<Value> {
+        match value {
+            Value::String(s) if s.contains('@') => Ok(Value::String(s.to_lowercase())),
+            other => Err(ResolverError::Argument(format!("Invalid email: {}", other))),
+        }
+    }
+
+    fn serialize(&self, value: Value) -> ResolverResult<Value> {
+        match value {
+            Value::String(s) => match s.split_once('@') {
+                Some((user, domain)) => {
+                    Ok(Value::String(format!("{}@{}", user, domain.to_uppercase())))
+                }
+                None => Err(ResolverError::Execution(format!("Invalid email: {}", s))),
+            },
+            other => Err(ResolverError::Execution(format!(
+                "Invalid email: {}",
+                other
+            ))),
+        }
+    }
+}
+
+struct EchoResolver;
+
+impl Resolver for EchoResolver {
+    fn name(&self) -> &'static str {
+        "echo"
+    }
+
+    fn resolve<'a>(
+        &'a self,
+        _ctx: &'a ResolverContext,
+        args: FxHashMap<String, Value>,
+    ) -> BoxFuture<'a, ResolverResult<Value>> {
+        Box::pin(async move { Ok(args.get("value").cloned().unwrap_or(Value::Null)) })
+    }
+}
+
+#[tokio::test]
+async fn test_custom_scalar_codec() {
+    let sdl = r#"
+        scalar Email
+        scalar Metadata
+
+        type Query {
+            email(value: Email!): Email @resolver(name: "echo")
+            metadata(value: Metadata!): Metadata @resolver(name: "echo")
+        }
+    "#;
+
+    let server = GraphQLServer::builder()
+        .sdl(sdl)
+        .register_resolver(EchoResolver)
+        .register_scalar_codec(EmailCodec)
+        .build()
+        .unwrap();
+
+    let response = server
+        .execute(r#"{ email(value: "Ada@Example.com") }"#)
+        .await;
+    assert!(response.errors.is_empty(), "Errors: {:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["email"], "ada@EXAMPLE.COM");
+
+    let response = server.execute(r#"{ email(value: "nope") }"#).await;
+    assert!(!response.errors.is_empty());
+
+    let response = server
+        .execute(r#"{ metadata(value: { tags: ["a"], count: 2 }) }"#)
+        .await;
+    assert!(response.errors.is_empty(), "Errors: {:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(
+        data["metadata"],
+        serde_json::json!({"tags": ["a"], "count": 2})
+    );
+}
+
+#[cfg(feature = "datetime")]
+#[tokio::test]
+async fn test_builtin_datetime_codec() {
+    let sdl = r#"
+        scalar DateTime
+
+        type Query {
+            at(value: DateTime!): DateTime @resolver(name: "echo")
+        }
+    "#;
+
+    let server = GraphQLServer::builder()
+        .sdl(sdl)
+        .register_resolver(EchoResolver)
+        .register_scalar_codec(graphql_resolver::DateTimeCodec)
+        .build()
+        .unwrap();
+
+    let response = server
+        .execute(r#"{ at(value: "2024-01-02T03:04:05+02:00") }"#)
+        .await;
+    assert!(response.errors.is_empty(), "Errors: {:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["at"], "2024-01-02T01:04:05Z");
+
+    let response = server.execute(r#"{ at(value: "tomorrow") }"#).await;
+    assert!(!response.errors.is_empty());
+}
+
+#[tokio::test]
+async fn test_argument_defaults_reach_resolvers() {
+    let sdl = r#"
+        type Query {
+            echoed(value: Int = 10): Int @resolver(name: "echo")
+            role(value: Role = MEMBER): Role @resolver(name: "echo")
+            called(limit: Int = 5): Int @call(resolver: "echo", args: { value: "$arg.limit" })
+        }
+
+        enum Role {
+            ADMIN
+            MEMBER
+        }
+    "#;
+
+    let server = GraphQLServer::builder()
+        .sdl(sdl)
+        .register_resolver(EchoResolver)
+        .build()
+        .unwrap();
+
+    let response = server.execute("{ echoed role called }").await;
+    assert!(response.errors.is_empty(), "Errors: {:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(
+        data,
+        serde_json::json!({"echoed": 10, "role": "MEMBER", "called": 5})
+    );
+
+    let response = server
+        .execute("{ echoed(value: 3) role(value: ADMIN) called(limit: 7) }")
+        .await;
+    assert!(response.errors.is_empty(), "Errors: {:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(
+        data,
+        serde_json::json!({"echoed": 3, "role": "ADMIN", "called": 7})
+    );
+
+    let response = server
+        .execute(r#"{ __type(name: "Query") { fields { name args { name defaultValue } } } }"#)
+        .await;
+    assert!(response.errors.is_empty(), "Errors: {:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    let defaults: Vec<_> = data["__type"]["fields"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| f["args"][0]["defaultValue"].clone())
+        .collect();
+    assert!(defaults.contains(&serde_json::json!("10")));
+    assert!(defaults.contains(&serde_json::json!("MEMBER")));
+    assert!(defaults.contains(&serde_json::json!("5")));
+}
+
+#[tokio::test]
+async fn test_descriptions_and_deprecations_in_introspection() {
+    let sdl = r#"
+        "Entry point"
+        type Query {
+            "Fetch a user"
+            user("The user id" id: ID!): User @resolver(name: "getUser")
+            me: User @deprecated(reason: "Use user")
+        }
+
+        "A registered user"
+        type User {
+            id: ID!
+            name: String!
+            role: Role
+        }
+
+        enum Role {
+            ADMIN
+            GUEST @deprecated
+        }
+    "#;
+
+    let server = GraphQLServer::builder()
+        .sdl(sdl)
+        .register_resolver(GetUserResolver)
+        .build()
+        .unwrap();
+
+    let response = server
+        .execute(
+            r#"{
+                query: __type(name: "Query") {
+                    description
+                    fields(includeDeprecated: true) {
+                        name
+                        description
+                        isDeprecated
+                        deprecationReason
+                        args { name description }
+                    }
+                }
+                user: __type(name: "User") { description }
+                role: __type(name: "Role") {
+                    enumValues(includeDeprecated: true) { name isDeprecated deprecationReason }
+                }
+            }"#,
+        )
+        .await;
+    assert!(response.errors.is_empty(), "Errors: {:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+
+    assert_eq!(data["query"]["description"], "Entry point");
+    assert_eq!(data["user"]["description"], "A registered user");
+
+    let fields = data["query"]["fields"].as_array().unwrap();
+    let user = fields.iter().find(|f| f["name"] == "user").unwrap();
+    assert_eq!(user["description"], "Fetch a user");
+    assert_eq!(user["isDeprecated"], false);
+    assert_eq!(user["args"][0]["description"], "The user id");
+    let me = fields.iter().find(|f| f["name"] == "me").unwrap();
+    assert_eq!(me["isDeprecated"], true);
+    assert_eq!(me["deprecationReason"], "Use user");
+
+    let guest = data["role"]["enumValues"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|v| v["name"] == "GUEST")
+        .unwrap();
+    assert_eq!(guest["isDeprecated"], true);
+    assert_eq!(guest["deprecationReason"], "No longer supported");
+}
+
+#[tokio::test]
+async fn test_request_and_shared_data_reach_resolvers() {
+    struct CurrentUser(String);
+    struct Tenant(&'static str);
+
+    struct ViewerResolver;
+
+    impl Resolver for ViewerResolver {
+        fn name(&self) -> &'static str {
+            "viewer"
+        }
+
+        fn resolve<'a>(
+            &'a self,
+            ctx: &'a ResolverContext,
+            _args: FxHashMap<String, Value>,
+        ) -> BoxFuture<'a, ResolverResult<Value>> {
+            Box::pin(async move {
+                let user = ctx
+                    .data::<CurrentUser>()
+                    .map(|u| u.0.clone())
+                    .ok_or_else(|| ResolverError::Execution("not authenticated".to_string()))?;
+                let tenant = ctx.data::<Tenant>().map(|t| t.0).unwrap_or_default();
+                Ok(serde_json::from_value(serde_json::json!({
+                    "id": user,
+                    "tenant": tenant,
+                }))
+                .unwrap())
+            })
+        }
+    }
+
+    struct GreetingsResolver;
+
+    impl ErasedBatchResolver for GreetingsResolver {
+        fn name(&self) -> &'static str {
+            "greetings"
+        }
+
+        fn batch_key_field(&self) -> &'static str {
+            "id"
+        }
+
+        fn load_erased<'a>(
+            &'a self,
+            ctx: &'a ResolverContext,
+            keys: Vec<serde_json::Value>,
+        ) -> BoxFuture<'a, ResolverResult<Vec<(serde_json::Value, serde_json::Value)>>> {
+            Box::pin(async move {
+                let tenant = ctx.data::<Tenant>().map(|t| t.0).unwrap_or_default();
+                let user = ctx.data::<CurrentUser>().map(|u| u.0.clone());
+                Ok(keys
+                    .into_iter()
+                    .map(|key| {
+                        let greeting = format!("{} from {} ({:?})", key, tenant, user);
+                        (key, serde_json::json!(greeting))
+                    })
+                    .collect())
+            })
+        }
+    }
+
+    let sdl = r#"
+        type Query {
+            viewer: Viewer @resolver(name: "viewer")
+        }
+
+        type Viewer {
+            id: ID!
+            tenant: String!
+            greeting: String @resolver(name: "greetings") @batchKey(field: "id")
+        }
+    "#;
+
+    let server = GraphQLServer::builder()
+        .sdl(sdl)
+        .data(Tenant("acme"))
+        .register_resolver(ViewerResolver)
+        .register_batch_resolver(GreetingsResolver)
+        .build()
+        .unwrap();
+
+    let request = async_graphql::Request::new("{ viewer { id tenant greeting } }")
+        .data(CurrentUser("alice".to_string()));
+    let response = server.execute_request(request).await;
+    assert!(response.errors.is_empty(), "Errors: {:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(
+        data,
+        serde_json::json!({"viewer": {
+            "id": "alice",
+            "tenant": "acme",
+            "greeting": "\"alice\" from acme (Some(\"alice\"))",
+        }})
+    );
+
+    let response = server.execute("{ viewer { id } }").await;
+    assert_eq!(response.errors.len(), 1);
+    assert!(response.errors[0].message.contains("not authenticated"));
+}
+
+#[tokio::test]
+async fn test_execute_request_with_variables_and_operation_name() {
+    let sdl = r#"
+        scalar Email
+
+        type Query {
+            echoed(value: Int = 10): Int @resolver(name: "echo")
+            email(value: Email!): Email @resolver(name: "echo")
+            filter(value: Filter!): FilterOut @resolver(name: "echo")
+        }
+
+        input Filter {
+            name: String!
+            limit: Int = 25
+        }
+
+        type FilterOut {
+            name: String!
+            limit: Int
+        }
+    "#;
+
+    let server = GraphQLServer::builder()
+        .sdl(sdl)
+        .register_resolver(EchoResolver)
+        .register_scalar_codec(EmailCodec)
+        .build()
+        .unwrap();
+
+    let query = r#"
+        query Echo($value: Int, $email: Email!, $filter: Filter!) {
+            echoed(value: $value)
+            email(value: $email)
+            filter(value: $filter) { name limit }
+        }
+
+        query Other { echoed }
+    "#;
+    let variables = async_graphql::Variables::from_json(serde_json::json!({
+        "value": 3,
+        "email": "Alice@Example.com",
+        "filter": {"name": "a"},
+    }));
+    let request = async_graphql::Request::new(query)
+        .variables(variables)
+        .operation_name("Echo");
+    let response = server.execute_request(request).await;
+    assert!(response.errors.is_empty(), "Errors: {:?}", response.errors);
+    assert_eq!(
+        response.data.into_json().unwrap(),
+        serde_json::json!({
+            "echoed": 3,
+            "email": "alice@EXAMPLE.COM",
+            "filter": {"name": "a", "limit": 25},
+        })
+    );
+
+    let request = async_graphql::Request::new(query).operation_name("Other");
+    let response = server.execute_request(request).await;
+    assert!(response.errors.is_empty(), "Errors: {:?}", response.errors);
+    assert_eq!(
+        response.data.into_json().unwrap(),
+        serde_json::json!({"echoed": 10})
+    );
+
+    let variables = async_graphql::Variables::from_json(serde_json::json!({
+        "email": "not-an-email",
+        "filter": {"name": "a"},
+    }));
+    let request = async_graphql::Request::new(query)
+        .variables(variables)
+        .operation_name("Echo");
+    let response = server.execute_request(request).await;
+    assert!(!response.errors.is_empty());
+}
+
+#[tokio::test]
+async fn test_execute_batch() {
+    let sdl = r#"
+        type Query {
+            echoed(value: Int = 10): Int @resolver(name: "echo")
+        }
+    "#;
+
+    let server = GraphQLServer::builder()
+        .sdl(sdl)
+        .register_resolver(EchoResolver)
+        .build()
+        .unwrap();
+
+    let query = "query ($value: Int) { echoed(value: $value) }";
+    let batch = async_graphql::BatchRequest::Batch(vec![
+        async_graphql::Request::new(query).variables(async_graphql::Variables::from_json(
+            serde_json::json!({"value": 1}),
+        )),
+        async_graphql::Request::new(query),
+        async_graphql::Request::new(query).variables(async_graphql::Variables::from_json(
+            serde_json::json!({"value": null}),
+        )),
+        async_graphql::Request::new("{ missing }"),
+    ]);
+
+    let responses = match server.execute_batch(batch).await {
+        async_graphql::BatchResponse::Batch(responses) => responses,
+        _ => panic!("Expected a batch response"),
+    };
+    assert_eq!(responses.len(), 4);
+    assert_eq!(
+        responses[0].data.clone().into_json().unwrap(),
+        serde_json::json!({"echoed": 1})
+    );
+    assert_eq!(
+        responses[1].data.clone().into_json().unwrap(),
+        serde_json::json!({"echoed": 10})
+    );
+    assert_eq!(
+        responses[2].data.clone().into_json().unwrap(),
+        serde_json::json!({"echoed": null})
+    );
+    assert!(!responses[3].errors.is_empty());
+}
+
+#[tokio::test]
+async fn test_resolvers_see_selection() {
+    use std::sync::{Arc, Mutex};
+
+    use graphql_resolver::SelectedField;
+
+    type Seen = Arc<Mutex<Vec<Vec<SelectedField>>>>;
+
+    struct ListUsersResolver(Seen);
+
+    impl Resolver for ListUsersResolver {
+        fn name(&self) -> &'static str {
+            "listUsers"
+        }
+
+        fn resolve<'a>(
+            &'a self,
+            ctx: &'a ResolverContext,
+            _args: FxHashMap<String, Value>,
+        ) -> BoxFuture<'a, ResolverResult<Value>> {
+            self.0.lock().unwrap().push(ctx.selection().to_vec());
+            Box::pin(async move {
+                Ok(serde_json::from_value(serde_json::json!([
+                    {"id": "1", "name": "Alice"},
+                    {"id": "2", "name": "Bob"},
+                ]))
+                .unwrap())
+            })
+        }
+    }
+
+    struct PostsByUserResolver(Seen);
+
+    impl ErasedBatchResolver for PostsByUserResolver {
+        fn name(&self) -> &'static str {
+            "getPostsByUser"
+        }
+
+        fn batch_key_field(&self) -> &'static str {
+            "id"
+        }
+
+        fn load_erased<'a>(
+            &'a self,
+            ctx: &'a ResolverContext,
+            keys: Vec<serde_json::Value>,
+        ) -> BoxFuture<'a, ResolverResult<Vec<(serde_json::Value, serde_json::Value)>>> {
+            self.0.lock().unwrap().push(ctx.selection().to_vec());
+            Box::pin(async move {
+                Ok(keys
+                    .into_iter()
+                    .map(|key| {
+                        let posts = serde_json::json!([{"id": key, "title": "t", "body": "b"}]);
+                        (key, posts)
+                    })
+                    .collect())
+            })
+        }
+    }
+
+    let sdl = r#"
+        type Query {
+            users: [User!]! @resolver(name: "listUsers")
+        }
+
+        type User {
+            id: ID!
+            name: String!
+            posts(first: Int = 10): [Post!]! @resolver(name: "getPostsByUser") @batchKey(field: "id")
+        }
+
+        type Post {
+            id: ID!
+            title: String!
+            body: String!
+        }
+    "#;
+
+    let users_seen = Seen::default();
+    let posts_seen = Seen::default();
+    let server = GraphQLServer::builder()
+        .sdl(sdl)
+        .register_resolver(ListUsersResolver(users_seen.clone()))
+        .register_batch_resolver(PostsByUserResolver(posts_seen.clone()))
+        .build()
+        .unwrap();
+
+    let response = server
+        .execute(
+            r#"
+            { users { id ...UserPosts } }
+            fragment UserPosts on User { recent: posts(first: 2) { title } }
+            "#,
+        )
+        .await;
+    assert!(response.errors.is_empty(), "Errors: {:?}", response.errors);
+
+    let users = users_seen.lock().unwrap();
+    assert_eq!(users.len(), 1);
+    let names: Vec<_> = users[0].iter().map(|f| f.name()).collect();
+    assert_eq!(names, ["id", "posts"]);
+
+    let posts = &users[0][1];
+    assert_eq!(posts.alias(), Some("recent"));
+    assert_eq!(
+        posts.arguments().get("first"),
+        Some(&Value::Number(2.into()))
+    );
+    assert_eq!(posts.selection(), [SelectedField::new("title")]);
+
+    let batches = posts_seen.lock().unwrap();
+    assert_eq!(batches.len(), 1);
+    assert_eq!(batches[0], [SelectedField::new("title")]);
+}
+
+#[tokio::test]
+async fn test_structured_errors_and_error_mapping() {
+    struct FailingResolver;
+
+    impl Resolver for FailingResolver {
+        fn name(&self) -> &'static str {
+            "fail"
+        }
+
+        fn resolve<'a>(
+            &'a self,
+            _ctx: &'a ResolverContext,
+            args: FxHashMap<String, Value>,
+        ) -> BoxFuture<'a, ResolverResult<Value>> {
+            Box::pin(async move {
+                match args.get("mode") {
+                    Some(Value::String(mode)) if mode == "forbidden" => {
+                        Err(ResolverError::new("Not allowed")
+                            .with_code("FORBIDDEN")
+                            .with_extension("requiredRole", "admin"))
+                    }
+                    Some(Value::String(mode)) if mode == "partial" => {
+                        Err(ResolverError::new("Some items failed")
+                            .with_code("PARTIAL")
+                            .with_data(Value::List(vec![Value::String("a".to_string())])))
+                    }
+                    _ => Err(ResolverError::Execution(
+                        "connection to db-01 refused".to_string(),
+                    )),
+                }
+            })
+        }
+    }
+
+    let sdl = r#"
+        type Query {
+            forbidden: String @call(resolver: "fail", args: { mode: "forbidden" })
+            partial: [String!] @call(resolver: "fail", args: { mode: "partial" })
+            internal: String @call(resolver: "fail", args: { mode: "internal" })
+        }
+    "#;
+
+    let server = GraphQLServer::builder()
+        .sdl(sdl)
+        .register_resolver(FailingResolver)
+        .build()
+        .unwrap();
+
+    let response = server.execute("{ forbidden partial internal }").await;
+    let json = serde_json::to_value(&response).unwrap();
+    assert_eq!(json["data"]["partial"], serde_json::json!(["a"]));
+    assert!(json["data"]["forbidden"].is_null());
+    assert!(json["data"]["internal"].is_null());
+
+    let errors = json["errors"].as_array().unwrap();
+    assert_eq!(errors.len(), 3);
+    let extensions_for = |message: &str| {
+        errors
+            .iter()
+            .find(|e| e["message"] == message)
+            .map(|e| e["extensions"].clone())
+            .unwrap()
+    };
+    assert_eq!(
+        extensions_for("Not allowed"),
+        serde_json::json!({"code": "FORBIDDEN", "requiredRole": "admin"})
+    );
+    assert_eq!(
+        extensions_for("Some items failed"),
+        serde_json::json!({"code": "PARTIAL"})
+    );
+    assert_eq!(
+        extensions_for("Execution error: connection to db-01 refused"),
+        serde_json::json!({"code": "EXECUTION_ERROR"})
+    );
+    let partial = errors
+        .iter()
+        .find(|e| e["message"] == "Some items failed")
+        .unwrap();
+    assert_eq!(partial["path"], serde_json::json!(["partial"]));
+
+    let server = GraphQLServer::builder()
+        .sdl(sdl)
+        .register_resolver(FailingResolver)
+        .map_error(|err| match err {
+            ResolverError::Execution(_) => {
+                Some(ResolverError::new("Internal server error").with_code("INTERNAL"))
+            }
+            _ => None,
+        })
+        .build()
+        .unwrap();
+
+    let response = server.execute("{ forbidden internal }").await;
+    let json = serde_json::to_value(&response).unwrap();
+    let errors = json["errors"].as_array().unwrap();
+    assert_eq!(errors.len(), 2);
+    assert!(errors.iter().any(|e| e["message"] == "Not allowed"));
+    assert!(!errors
+        .iter()
+        .any(|e| e["message"].as_str().unwrap().contains("db-01")));
+    let internal = errors
+        .iter()
+        .find(|e| e["message"] == "Internal server error")
+        .unwrap();
+    assert_eq!(
+        internal["extensions"],
+        serde_json::json!({"code": "INTERNAL"})
+    );
+}
+
+#[tokio::test]
+async fn test_batch_errors_only_affect_their_parents() {
+    use graphql_resolver::BatchResolver;
+
+    #[derive(Clone, serde::Serialize)]
+    struct Author {
+        id: String,
+        name: String,
+    }
+
+    struct AuthorsResolver;
+
+    impl BatchResolver for AuthorsResolver {
+        type Key = String;
+        type Value = Author;
+
+        fn load<'a>(
+            &'a self,
+            _ctx: &'a ResolverContext,
+            _keys: Vec<String>,
+        ) -> BoxFuture<'a, ResolverResult<Vec<(String, Author)>>> {
+            Box::pin(async move { Err(ResolverError::Execution("unused".to_string())) })
+        }
+
+        fn load_each<'a>(
+            &'a self,
+            _ctx: &'a ResolverContext,
+            keys: Vec<String>,
+        ) -> BoxFuture<'a, ResolverResult<Vec<(String, ResolverResult<Author>)>>> {
+            Box::pin(async move {
+                Ok(keys
+                    .into_iter()
+                    .filter(|key| key != "missing")
+                    .map(|key| {
+                        let author = if key == "banned" {
+                            Err(ResolverError::new("Author is banned").with_code("FORBIDDEN"))
+                        } else {
+                            Ok(Author {
+                                id: key.clone(),
+                                name: format!("Author {}", key),
+                            })
+                        };
+                        (key, author)
+                    })
+                    .collect())
+            })
+        }
+
+        fn name(&self) -> &'static str {
+            "getAuthorsByIds"
+        }
+
+        fn batch_key_field(&self) -> &'static str {
+            "authorId"
+        }
+    }
+
+    struct ListPostsResolver;
+
+    impl Resolver for ListPostsResolver {
+        fn name(&self) -> &'static str {
+            "listPosts"
+        }
+
+        fn resolve<'a>(
+            &'a self,
+            _ctx: &'a ResolverContext,
+            _args: FxHashMap<String, Value>,
+        ) -> BoxFuture<'a, ResolverResult<Value>> {
+            Box::pin(async move {
+                Ok(serde_json::from_value(serde_json::json!([
+                    {"id": "p1", "authorId": "a1"},
+                    {"id": "p2", "authorId": "banned"},
+                    {"id": "p3", "authorId": "missing"},
+                    {"id": "p4", "authorId": 7},
+                ]))
+                .unwrap())
+            })
+        }
+    }
+
+    let sdl = r#"
+        type Query {
+            posts: [Post!]! @resolver(name: "listPosts")
+        }
+
+        type Post {
+            id: ID!
+            authorId: ID!
+            author: Author @resolver(name: "getAuthorsByIds") @batchKey(field: "authorId")
+        }
+
+        type Author {
+            id: ID!
+            name: String!
+        }
+    "#;
+
+    let server = GraphQLServer::builder()
+        .sdl(sdl)
+        .register_resolver(ListPostsResolver)
+        .register_batch_resolver(AuthorsResolver)
+        .build()
+        .unwrap();
+
+    let response = server.execute(r#"{ posts { id author { name } } }"#).await;
+    let json = serde_json::to_value(&response).unwrap();
+
+    let posts = json["data"]["posts"].as_array().unwrap();
+    assert_eq!(posts.len(), 4);
+    assert_eq!(posts[0]["author"]["name"], "Author a1");
+    assert!(posts[1]["author"].is_null());
+    assert!(posts[2]["author"].is_null());
+    assert!(posts[3]["author"].is_null());
+
+    let errors = json["errors"].as_array().unwrap();
+    assert_eq!(errors.len(), 2, "Errors: {:?}", errors);
+    let banned = errors
+        .iter()
+        .find(|e| e["message"] == "Author is banned")
+        .unwrap();
+    assert_eq!(banned["path"], serde_json::json!(["posts", 1, "author"]));
+    assert_eq!(banned["extensions"]["code"], "FORBIDDEN");
+    let invalid = errors
+        .iter()
+        .find(|e| e["message"].as_str().unwrap().contains("Invalid batch key"))
+        .unwrap();
+    assert_eq!(invalid["path"], serde_json::json!(["posts", 3, "author"]));
+    assert_eq!(invalid["extensions"]["code"], "EXECUTION_ERROR");
+}
+
+#[tokio::test]
+async fn test_derived_resolvers() {
+    use graphql_resolver::{ExecuteResolver, TraitResolver};
+
+    #[derive(serde::Deserialize)]
+    struct GreetArgs {
+        name: String,
+        times: Option<usize>,
+    }
+
+    #[derive(serde::Serialize)]
+    struct Greeting {
+        message: String,
+    }
+
+    #[derive(Default, TraitResolver)]
+    #[resolver(name = "greet", args = GreetArgs)]
+    struct GreetResolver;
+
+    impl ExecuteResolver<GreetArgs> for GreetResolver {
+        type Output = Greeting;
+
+        fn execute<'a>(
+            &'a self,
+            _ctx: &'a ResolverContext,
+            args: GreetArgs,
+        ) -> BoxFuture<'a, ResolverResult<Greeting>> {
+            Box::pin(async move {
+                Ok(Greeting {
+                    message: format!("Hello {}", args.name).repeat(args.times.unwrap_or(1)),
+                })
+            })
+        }
+    }
+
+    #[derive(Default, TraitResolver)]
+    #[resolver(name = "argCount")]
+    struct ArgCountResolver;
+
+    impl ExecuteResolver for ArgCountResolver {
+        type Output = usize;
+
+        fn execute<'a>(
+            &'a self,
+            _ctx: &'a ResolverContext,
+            args: FxHashMap<String, Value>,
+        ) -> BoxFuture<'a, ResolverResult<usize>> {
+            Box::pin(async move { Ok(args.len()) })
+        }
+    }
+
+    let sdl = r#"
+        type Query {
+            greet(name: String!, times: Int): Greeting @resolver(name: "greet")
+            badGreet(name: Int!): Greeting @resolver(name: "greet")
+            argCount(a: Int, b: Int): Int @resolver(name: "argCount")
+        }
+
+        type Greeting {
+            message: String!
+        }
+    "#;
+
+    let server = GraphQLServer::builder()
+        .sdl(sdl)
+        .register_resolver(GreetResolver)
+        .register_resolver(ArgCountResolver)
+        .build()
+        .unwrap();
+
+    let response = server
+        .execute(r#"{ greet(name: "Ann", times: 2) { message } argCount(a: 1, b: 2) }"#)
+        .await;
+    assert!(response.errors.is_empty(), "Errors: {:?}", response.errors);
+    assert_eq!(
+        response.data.into_json().unwrap(),
+        serde_json::json!({"greet": {"message": "Hello AnnHello Ann"}, "argCount": 2})
+    );
+
+    let response = server.execute("{ badGreet(name: 1) { message } }").await;
+    assert_eq!(response.errors.len(), 1);
+    assert!(response.errors[0]
+        .message
+        .contains("Invalid arguments for resolver 'greet'"));
+}
+
+#[tokio::test]
+async fn test_derived_batch_resolver() {
+    use graphql_resolver::{BatchResolver, LoadBatchResolver, TraitResolver};
+
+    #[derive(Clone, serde::Serialize)]
+    struct Author {
+        id: u32,
+        name: String,
+    }
+
+    #[derive(Default, TraitResolver)]
+    #[batch_resolver(name = "getDerivedAuthors", batch_key = "authorId")]
+    struct DerivedAuthorsResolver;
+
+    impl LoadBatchResolver for DerivedAuthorsResolver {
+        type Key = u32;
+        type Value = Author;
+
+        fn load<'a>(
+            &'a self,
+            _ctx: &'a ResolverContext,
+            keys: Vec<u32>,
+        ) -> BoxFuture<'a, ResolverResult<Vec<(u32, Author)>>> {
+            Box::pin(async move {
+                Ok(keys
+                    .into_iter()
+                    .map(|id| {
+                        let author = Author {
+                            id,
+                            name: format!("Author {}", id),
+                        };
+                        (id, author)
+                    })
+                    .collect())
+            })
+        }
+    }
+
+    struct ListPostsResolver;
+
+    impl Resolver for ListPostsResolver {
+        fn name(&self) -> &'static str {
+            "listPosts"
+        }
+
+        fn resolve<'a>(
+            &'a self,
+            _ctx: &'a ResolverContext,
+            _args: FxHashMap<String, Value>,
+        ) -> BoxFuture<'a, ResolverResult<Value>> {
+            Box::pin(async move {
+                let posts = serde_json::json!([
+                    {"id": "p1", "authorId": 1},
+                    {"id": "p2", "authorId": 2}
+                ]);
+                Ok(serde_json::from_value(posts).unwrap())
+            })
+        }
+    }
+
+    assert_eq!(
+        BatchResolver::name(&DerivedAuthorsResolver),
+        "getDerivedAuthors"
+    );
+    assert_eq!(
+        BatchResolver::batch_key_field(&DerivedAuthorsResolver),
+        "authorId"
+    );
+
+    let sdl = r#"
+        type Query {
+            posts: [Post!]! @resolver(name: "listPosts")
+        }
+
+        type Post {
+            id: ID!
+            authorId: Int!
+            author: Author @resolver(name: "getDerivedAuthors") @batchKey(field: "authorId")
+        }
+
+        type Author {
+            id: Int!
+            name: String!
+        }
+    "#;
+
+    let server = GraphQLServer::builder()
+        .sdl(sdl)
+        .register_resolver(ListPostsResolver)
+        .register_batch_resolver(DerivedAuthorsResolver)
+        .build()
+        .unwrap();
+
+    let response = server.execute(r#"{ posts { author { id name } } }"#).await;
+    assert!(response.errors.is_empty(), "Errors: {:?}", response.errors);
+    assert_eq!(
+        response.data.into_json().unwrap(),
+        serde_json::json!({"posts": [
+            {"author": {"id": 1, "name": "Author 1"}},
+            {"author": {"id": 2, "name": "Author 2"}}
+        ]})
+    );
+}
+
+#[tokio::test]
+async fn test_fn_resolvers() {
+    #[derive(serde::Serialize)]
+    struct User {
+        id: String,
+        name: String,
+    }
+
+    #[graphql_resolver::resolver(name = "getFnUser")]
+    async fn get_fn_user(
+        _ctx: &ResolverContext,
+        user_id: String,
+        suffix: Option<String>,
+    ) -> Result<User, ResolverError> {
+        if user_id == "missing" {
+            return Err(ResolverError::new("User not found"));
+        }
+        Ok(User {
+            name: format!("User {}{}", user_id, suffix.unwrap_or_default()),
+            id: user_id,
+        })
+    }
+
+    #[graphql_resolver::resolver]
+    async fn count_fn_users() -> ResolverResult<i32> {
+        Ok(3)
+    }
+
+    assert_eq!(GetFnUserResolver.name(), "getFnUser");
+    assert_eq!(CountFnUsersResolver.name(), "count_fn_users");
+
+    let sdl = r#"
+        type Query {
+            user(userId: ID!, suffix: String): User @resolver(name: "getFnUser")
+            badUser(userId: Int!): User @resolver(name: "getFnUser")
+            count: Int! @resolver(name: "count_fn_users")
+        }
+
+        type User {
+            id: ID!
+            name: String!
+        }
+    "#;
+
+    let server = GraphQLServer::builder()
+        .sdl(sdl)
+        .register_resolver(GetFnUserResolver)
+        .register_resolver(CountFnUsersResolver)
+        .build()
+        .unwrap();
+
+    let response = server
+        .execute(r#"{ a: user(userId: "1") { id name } b: user(userId: "2", suffix: "!") { name } count }"#)
+        .await;
+    assert!(response.errors.is_empty(), "Errors: {:?}", response.errors);
+    assert_eq!(
+        response.data.into_json().unwrap(),
+        serde_json::json!({
+            "a": {"id": "1", "name": "User 1"},
+            "b": {"name": "User 2!"},
+            "count": 3
+        })
+    );
+
+    let response = server
+        .execute(r#"{ user(userId: "missing") { id } }"#)
+        .await;
+    assert_eq!(response.errors.len(), 1);
+    assert_eq!(response.errors[0].message, "User not found");
+
+    let response = server.execute("{ badUser(userId: 1) { id } }").await;
+    assert_eq!(response.errors.len(), 1);
+    assert!(response.errors[0]
+        .message
+        .contains("Invalid argument 'userId' for resolver 'getFnUser'"));
+}
+
+#[tokio::test]
+async fn test_with_inventory_registers_submitted_resolvers() {
+    #[graphql_resolver::resolver(name = "inventoryEcho")]
+    async fn inventory_echo(text: String) -> ResolverResult<String> {
+        Ok(text)
+    }
+
+    let sdl = r#"
+        type Query {
+            echo(text: String!): String! @resolver(name: "inventoryEcho")
+        }
+    "#;
+
+    let server = GraphQLServer::builder()
+        .sdl(sdl)
+        .with_inventory()
+        .build()
+        .unwrap();
+    assert!(server.registry().get_resolver("inventoryEcho").is_ok());
+
+    let response = server.execute(r#"{ echo(text: "hi") }"#).await;
+    assert!(response.errors.is_empty(), "Errors: {:?}", response.errors);
+    assert_eq!(
+        response.data.into_json().unwrap(),
+        serde_json::json!({"echo": "hi"})
+    );
+
+    let result = GraphQLServer::builder()
+        .sdl(sdl)
+        .register_resolver(InventoryEchoResolver)
+        .with_inventory()
         .build();
+    match result {
+        Err(ServerError::Config(msg)) => assert_eq!(
+            msg,
+            "resolver 'inventoryEcho' is registered both explicitly and through inventory"
+        ),
+        _ => panic!("Expected Config error"),
+    }
+}
+
+#[tokio::test]
+async fn test_duplicate_resolver_names_fail_validation() {
+    struct ConstResolver(i32);
+
+    impl Resolver for ConstResolver {
+        fn name(&self) -> &'static str {
+            "getValue"
+        }
+
+        fn resolve<'a>(
+            &'a self,
+            _ctx: &'a ResolverContext,
+            _args: FxHashMap<String, Value>,
+        ) -> BoxFuture<'a, ResolverResult<Value>> {
+            Box::pin(async move { Ok(Value::from(self.0)) })
+        }
+    }
+
+    let sdl = r#"
+        type Query {
+            value: Int! @resolver(name: "getValue")
+        }
+    "#;
+
+    let result = GraphQLServer::builder()
+        .sdl(sdl)
+        .register_resolver(ConstResolver(1))
+        .register_resolver(ConstResolver(2))
+        .validate();
+    match result {
+        Err(ServerError::Config(msg)) => {
+            assert_eq!(msg, "resolver 'getValue' is registered more than once")
+        }
+        _ => panic!("Expected Config error"),
+    }
+
+    let server = GraphQLServer::builder()
+        .sdl(sdl)
+        .register_resolver(ConstResolver(1))
+        .register_resolver(ConstResolver(3))
+        .replace_resolver(ConstResolver(2))
+        .build()
+        .unwrap();
+    let response = server.execute("{ value }").await;
+    assert_eq!(
+        response.data.into_json().unwrap(),
+        serde_json::json!({"value": 2})
+    );
+}
+
+#[test]
+fn test_validate_checks_resolver_targets() {
+    struct NamedResolver(&'static str);
+
+    impl Resolver for NamedResolver {
+        fn name(&self) -> &'static str {
+            self.0
+        }
+
+        fn resolve<'a>(
+            &'a self,
+            _ctx: &'a ResolverContext,
+            _args: FxHashMap<String, Value>,
+        ) -> BoxFuture<'a, ResolverResult<Value>> {
+            Box::pin(async { Ok(Value::Null) })
+        }
+    }
+
+    struct NamedBatchResolver(&'static str);
+
+    impl ErasedBatchResolver for NamedBatchResolver {
+        fn name(&self) -> &'static str {
+            self.0
+        }
+
+        fn batch_key_field(&self) -> &'static str {
+            "authorId"
+        }
+
+        fn load_erased<'a>(
+            &'a self,
+            _ctx: &'a ResolverContext,
+            _keys: Vec<serde_json::Value>,
+        ) -> BoxFuture<'a, ResolverResult<Vec<(serde_json::Value, serde_json::Value)>>> {
+            Box::pin(async { Ok(Vec::new()) })
+        }
+    }
+
+    let sdl = r#"
+        type Query {
+            user: User @resolver(name: "getUsr")
+            post: Post @resolver(name: "getPost")
+        }
+
+        type Post {
+            authorId: ID!
+            author: User @resolver(name: "getAuthors")
+            editor: User @resolver(name: "getPost") @batchKey(field: "authorId")
+            reviewer: User @call(resolver: "getReviewer", args: { id: "$parent.authorId" })
+        }
+
+        type User {
+            id: ID!
+        }
+    "#;
+
+    let result = GraphQLServer::builder()
+        .sdl(sdl)
+        .register_resolver(NamedResolver("getPost"))
+        .register_batch_resolver(NamedBatchResolver("getAuthors"))
+        .skip_n1_validation()
+        .validate();
+    let errors = match result {
+        Err(ServerError::Validation(errors)) => errors,
+        _ => panic!("Expected Validation error"),
+    };
+    let messages: Vec<_> = errors
+        .iter()
+        .map(|e| {
+            let position = e.position.as_ref().unwrap();
+            format!(
+                "{}.{} at {}: {}",
+                e.type_name, e.field_name, position, e.message
+            )
+        })
+        .collect();
+    assert_eq!(
+        messages,
+        [
+            "Post.author at <sdl 1>:9:13: 'getAuthors' is a batch resolver and needs @batchKey(field: \"authorId\")",
+            "Post.editor at <sdl 1>:10:13: 'getPost' is not a batch resolver and cannot be used with @batchKey",
+            "Post.reviewer at <sdl 1>:11:13: @call target 'getReviewer' is not registered",
+            "Query.user at <sdl 1>:3:13: resolver 'getUsr' is not registered",
+        ]
+    );
+
+    let sdl = r#"
+        type Query {
+            post: Post @resolver(name: "getPost")
+        }
+
+        type Post {
+            id: ID!
+        }
+    "#;
+    let validated = GraphQLServer::builder()
+        .sdl(sdl)
+        .register_resolver(NamedResolver("getPost"))
+        .register_resolver(NamedResolver("getUnused"))
+        .register_batch_resolver(NamedBatchResolver("getAuthors"))
+        .validate()
+        .unwrap();
+    assert_eq!(
+        validated.warnings(),
+        [
+            "resolver 'getUnused' is registered but no field references it",
+            "batch resolver 'getAuthors' is registered but no field references it",
+        ]
+    );
+}
+
+#[test]
+fn test_validate_checks_parent_and_argument_references() {
+    struct NamedResolver(&'static str);
+
+    impl Resolver for NamedResolver {
+        fn name(&self) -> &'static str {
+            self.0
+        }
+
+        fn resolve<'a>(
+            &'a self,
+            _ctx: &'a ResolverContext,
+            _args: FxHashMap<String, Value>,
+        ) -> BoxFuture<'a, ResolverResult<Value>> {
+            Box::pin(async { Ok(Value::Null) })
+        }
+    }
+
+    struct AuthorsResolver;
+
+    impl ErasedBatchResolver for AuthorsResolver {
+        fn name(&self) -> &'static str {
+            "getAuthors"
+        }
+
+        fn batch_key_field(&self) -> &'static str {
+            "authorId"
+        }
+
+        fn load_erased<'a>(
+            &'a self,
+            _ctx: &'a ResolverContext,
+            _keys: Vec<serde_json::Value>,
+        ) -> BoxFuture<'a, ResolverResult<Vec<(serde_json::Value, serde_json::Value)>>> {
+            Box::pin(async { Ok(Vec::new()) })
+        }
+    }
+
+    let sdl = r#"
+        type Query {
+            post: Post @resolver(name: "getPost")
+        }
+
+        type Post {
+            authorId: ID!
+            author: User @resolver(name: "getAuthors") @batchKey(field: "authrId")
+            reviewer(first: Int): User @call(resolver: "getUser", args: { id: "$parent.userId", limit: "$arg.frist", kind: "review" })
+        }
+
+        type User {
+            id: ID!
+        }
+    "#;
+
+    let result = GraphQLServer::builder()
+        .sdl(sdl)
+        .register_resolver(NamedResolver("getPost"))
+        .register_resolver(NamedResolver("getUser"))
+        .register_batch_resolver(AuthorsResolver)
+        .skip_n1_validation()
+        .validate();
+    let errors = match result {
+        Err(ServerError::Validation(errors)) => errors,
+        _ => panic!("Expected Validation error"),
+    };
+    let messages: Vec<_> = errors
+        .iter()
+        .map(|e| {
+            let position = e.position.as_ref().unwrap();
+            format!(
+                "{}.{} at {}: {}",
+                e.type_name, e.field_name, position, e.message
+            )
+        })
+        .collect();
+    assert_eq!(
+        messages,
+        [
+            "Post.author at <sdl 1>:8:56: @batchKey field 'authrId' does not exist on type 'Post'",
+            "Post.reviewer at <sdl 1>:9:40: @call argument 'id' references '$parent.userId', but type 'Post' has no field 'userId'",
+            "Post.reviewer at <sdl 1>:9:40: @call argument 'limit' references '$arg.frist', but field 'reviewer' has no argument 'frist'",
+        ]
+    );
+}
 
+#[test]
+fn test_errors_report_sdl_part_positions() {
+    let query_sdl = "type Query {\n  users: [User!]!\n}";
+    let user_sdl =
+        "type User {\n  id: ID!\n  posts: [Post!]! @resolver(name: \"getPostsByUser\")\n}";
+
+    let result = GraphQLServer::builder()
+        .sdl_named("query.graphql", query_sdl)
+        .sdl_named("user.graphql", user_sdl)
+        .sdl_named("post.graphql", "type Post {\n  id ID!\n}")
+        .validate();
+    let err = match result {
+        Err(ServerError::Parse(err)) => err,
+        _ => panic!("Expected Parse error"),
+    };
+    let position = err.position.as_ref().unwrap();
+    assert_eq!(position.to_string(), "post.graphql:2:6");
+    assert!(err
+        .to_string()
+        .ends_with("\n --> post.graphql:2:6\n  |\n2 |   id ID!\n  |      ^"));
+
+    let result = GraphQLServer::builder()
+        .sdl_named("query.graphql", query_sdl)
+        .sdl_named("user.graphql", user_sdl)
+        .sdl_named("post.graphql", "type Post {\n  id: ID!\n}")
+        .validate();
+    let errors = match result {
+        Err(ServerError::N1Detection(errors)) => errors,
+        _ => panic!("Expected N1Detection error"),
+    };
+    assert_eq!(
+        errors[0].position.as_ref().unwrap().to_string(),
+        "user.graphql:3:3"
+    );
+    assert!(errors[0].to_string().ends_with(
+        "\n --> user.graphql:3:3\n  |\n3 |   posts: [Post!]! @resolver(name: \"getPostsByUser\")\n  |   ^"
+    ));
+
+    let result = GraphQLServer::builder()
+        .sdl_named("query.graphql", query_sdl)
+        .sdl_named("user.graphql", user_sdl)
+        .sdl_named("post.graphql", "type Post {\n  id: ID!\n}")
+        .skip_n1_validation()
+        .validate();
+    let err = result.err().unwrap();
+    assert!(matches!(err, ServerError::Validation(_)));
+    assert_eq!(
+        err.to_string(),
+        "Resolver validation failed:\n\
+         Invalid field User.posts: resolver 'getPostsByUser' is not registered\n \
+         --> user.graphql:3:3\n  |\n3 |   posts: [Post!]! @resolver(name: \"getPostsByUser\")\n  |   ^"
+    );
+}
+
+#[test]
+fn test_sdl_files_and_directories() {
+    let dir = std::env::temp_dir().join(format!("graphql-resolver-sdl-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("users")).unwrap();
+    std::fs::write(
+        dir.join("query.graphql"),
+        "type Query {\n  user(id: ID!): User @resolver(name: \"getUser\")\n}\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("users/user.graphql"),
+        "type User {\n  id: ID!\n  name: String!\n  avatar: String @resolver(name: \"getAvatar\")\n}\n",
+    )
+    .unwrap();
+    std::fs::write(dir.join("README.md"), "# Schema").unwrap();
+
+    let result = GraphQLServer::builder()
+        .sdl_dir(&dir, "**/*.graphql")
+        .register_resolver(GetUserResolver)
+        .validate();
+    let errors = match result {
+        Err(ServerError::Validation(errors)) => errors,
+        _ => panic!("Expected Validation error"),
+    };
+    let position = errors[0].position.as_ref().unwrap();
+    assert_eq!(errors[0].field_name, "avatar");
+    assert_eq!(
+        position.source,
+        dir.join("users/user.graphql").display().to_string()
+    );
+    assert_eq!((position.line, position.column), (4, 3));
+
+    let result = GraphQLServer::builder()
+        .sdl_file(dir.join("query.graphql"))
+        .sdl_file(dir.join("users/user.graphql"))
+        .register_resolver(GetUserResolver)
+        .skip_resolver_validation()
+        .build();
     assert!(result.is_ok());
+
+    let result = GraphQLServer::builder()
+        .sdl_dir(&dir, "*.gql")
+        .sdl_file(dir.join("missing.graphql"))
+        .validate();
+    match result {
+        Err(ServerError::Config(msg)) => {
+            assert!(msg.starts_with(&format!(
+                "No SDL files in '{}' match '*.gql'; Failed to read SDL file '{}'",
+                dir.display(),
+                dir.join("missing.graphql").display()
+            )));
+        }
+        _ => panic!("Expected Config error"),
+    }
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_modular_schema_with_extensions_and_merged_types() {
+    let users_sdl = r#"
+        type Query {
+            user(id: ID!): User @resolver(name: "getUser")
+        }
+
+        type User {
+            id: ID!
+        }
+    "#;
+    let profiles_sdl = r#"
+        extend type User {
+            name: String!
+        }
+    "#;
+    let admin_sdl = r#"
+        type Query {
+            admin(id: ID!): User @resolver(name: "getUser")
+        }
+    "#;
+
+    let result = GraphQLServer::builder()
+        .sdl_named("users.graphql", users_sdl)
+        .sdl_named("profiles.graphql", profiles_sdl)
+        .sdl_named("admin.graphql", admin_sdl)
+        .register_resolver(GetUserResolver)
+        .build();
+    let errors = match result {
+        Err(ServerError::Conflict(errors)) => errors,
+        _ => panic!("Expected Conflict error"),
+    };
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors[0].message,
+        "type 'Query' is already defined at users.graphql:2:9"
+    );
+    assert_eq!(
+        errors[0].position.as_ref().unwrap().to_string(),
+        "admin.graphql:2:9"
+    );
+
+    let server = GraphQLServer::builder()
+        .sdl_named("users.graphql", users_sdl)
+        .sdl_named("profiles.graphql", profiles_sdl)
+        .sdl_named("admin.graphql", admin_sdl)
+        .register_resolver(GetUserResolver)
+        .merge_types()
+        .build()
+        .unwrap();
+
+    let response = server
+        .execute(r#"{ user(id: "1") { id name } admin(id: "2") { name } }"#)
+        .await;
+    assert!(response.errors.is_empty(), "Errors: {:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(data["user"]["name"], "User 1");
+    assert_eq!(data["admin"]["name"], "User 2");
+
+    let result = GraphQLServer::builder()
+        .sdl_named("users.graphql", users_sdl)
+        .sdl_named(
+            "posts.graphql",
+            "type Query {\n  user(id: ID!): User @resolver(name: \"getPostAuthor\")\n}",
+        )
+        .register_resolver(GetUserResolver)
+        .merge_types()
+        .build();
+    let err = result.err().unwrap();
+    assert!(matches!(err, ServerError::Conflict(_)));
+    assert_eq!(
+        err.to_string(),
+        "Conflicting SDL definitions:\n\
+         field 'Query.user' does not match its definition at users.graphql:3:13\n \
+         --> posts.graphql:2:3\n  |\n2 |   user(id: ID!): User @resolver(name: \"getPostAuthor\")\n  |   ^"
+    );
 }